@@ -0,0 +1,105 @@
+//! Price lookups `validate_timeout` does for one open request: asset by id, base/collateral
+//! and quote/collateral pairs. `snapshot_scan` replays the previous implementation, which
+//! copied the whole bid-ask table and scanned it for every leg. `bid_ask_index` is what the
+//! `BidAskCache` does now.
+//!
+//! Run with `cargo bench --bench open_flow_price_lookups`.
+
+use std::{hint::black_box, sync::Arc};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+#[allow(dead_code, unused_imports)]
+#[path = "../src/caches/bid_ask_index.rs"]
+mod bid_ask_index;
+
+use bid_ask_index::BidAskIndex;
+
+const CURRENCIES: [&str; 10] = [
+    "USD", "EUR", "GBP", "JPY", "CHF", "AUD", "CAD", "NZD", "SEK", "NOK",
+];
+
+const STOCKS_AMOUNT: usize = 1000;
+
+struct BidAsk {
+    row_key: String,
+    base: String,
+    quote: String,
+}
+
+fn generate_bid_asks() -> Vec<Arc<BidAsk>> {
+    let mut result = vec![];
+
+    for base in CURRENCIES {
+        for quote in CURRENCIES {
+            if base != quote {
+                result.push(Arc::new(BidAsk {
+                    row_key: format!("{}{}", base, quote),
+                    base: base.to_string(),
+                    quote: quote.to_string(),
+                }));
+            }
+        }
+    }
+
+    for i in 0..STOCKS_AMOUNT {
+        result.push(Arc::new(BidAsk {
+            row_key: format!("STOCK{}", i),
+            base: format!("STOCK{}", i),
+            quote: "USD".to_string(),
+        }));
+    }
+
+    result
+}
+
+fn find_in_snapshot<'s>(
+    snapshot: &'s [Arc<BidAsk>],
+    first: &str,
+    second: &str,
+) -> Option<&'s Arc<BidAsk>> {
+    snapshot
+        .iter()
+        .find(|x| (x.base == first && x.quote == second) || (x.base == second && x.quote == first))
+}
+
+fn open_flow_price_lookups(c: &mut Criterion) {
+    let bid_asks = generate_bid_asks();
+    let asset_id = format!("STOCK{}", STOCKS_AMOUNT - 1);
+
+    let mut index = BidAskIndex::new();
+    for bid_ask in &bid_asks {
+        index.insert(
+            &bid_ask.row_key,
+            &bid_ask.base,
+            &bid_ask.quote,
+            bid_ask.clone(),
+        );
+    }
+
+    let mut group = c.benchmark_group("open_flow_price_lookups");
+
+    group.bench_function("snapshot_scan", |b| {
+        b.iter(|| {
+            let snapshot = bid_asks.clone();
+            let base_collateral = find_in_snapshot(&snapshot, &asset_id, "EUR").is_some();
+            let quote_collateral = find_in_snapshot(&snapshot, "USD", "EUR").is_some();
+            let asset = snapshot.iter().any(|x| x.row_key == asset_id);
+            black_box((base_collateral, quote_collateral, asset))
+        })
+    });
+
+    group.bench_function("bid_ask_index", |b| {
+        b.iter(|| {
+            let base_collateral = index.find_pair(&asset_id, "EUR").is_some();
+            let quote_collateral = index.find_pair("USD", "EUR").is_some();
+            let asset = index.get_by_id(&asset_id).is_some();
+            black_box((base_collateral, quote_collateral, asset))
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, open_flow_price_lookups);
+criterion_main!(benches);
@@ -2,12 +2,12 @@ fn main() {
 
     let url = "https://raw.githubusercontent.com/my-cfd-platform/proto-files/main/proto/";
 
-    ci_utils::sync_and_build_proto_file_with_builder(url, "TradingExecutorGrpcService.proto", |x| {
-        x.type_attribute(
-            ".",
-            "#[derive(serde::Serialize,serde::Deserialize)]"
-        )
-    });
+    // The executor owns its contract, so it is built from the local copy instead of being
+    // synced from proto-files. Changes are published upstream from here.
+    tonic_build::configure()
+        .type_attribute(".", "#[derive(serde::Serialize,serde::Deserialize)]")
+        .compile(&["proto/TradingExecutorGrpcService.proto"], &["proto"])
+        .unwrap();
 
     ci_utils::sync_and_build_proto_file_with_builder(url, "ABookBridge.proto", |x| {
         x.type_attribute(
@@ -5,7 +5,12 @@ use service_sdk::{
     ServiceContext,
 };
 
-use crate::{ABookBridgeGrpcClient, AccountsManagerGrpcClient, PositionManagerGrpcClient};
+use crate::{
+    ABookBridgeGrpcClient, AccountLeverageNoSqlEntity, AccountPositionsPollers,
+    AccountsManagerGrpcClient, BidAskCache, ClosedPositionsHistory, InstrumentHolidayNoSqlEntity,
+    InstrumentTradingSettingsNoSqlEntity, InvalidSettingsLog, MarketStatePolicy,
+    PositionManagerGrpcClient, TradingProfileInstrumentSettingsNoSqlEntity,
+};
 use my_nosql_contracts::{
     BidAskSnapshotNoSqlEntity, TradingGroupNoSqlEntity, TradingInstrumentNoSqlEntity,
     TradingProfileNoSqlEntity,
@@ -22,13 +27,24 @@ pub struct AppContext {
     pub trading_groups_reader: Arc<MyNoSqlDataReaderTcp<TradingGroupNoSqlEntity>>,
     pub trading_profiles_reader: Arc<MyNoSqlDataReaderTcp<TradingProfileNoSqlEntity>>,
     pub bid_ask_snapshot_ns_reader: Arc<MyNoSqlDataReaderTcp<BidAskSnapshotNoSqlEntity>>,
+    pub bid_ask_cache: Arc<BidAskCache>,
+    pub instrument_holidays_reader: Arc<MyNoSqlDataReaderTcp<InstrumentHolidayNoSqlEntity>>,
+    pub instrument_trading_settings_reader:
+        Arc<MyNoSqlDataReaderTcp<InstrumentTradingSettingsNoSqlEntity>>,
+    pub trading_profile_instrument_settings_reader:
+        Arc<MyNoSqlDataReaderTcp<TradingProfileInstrumentSettingsNoSqlEntity>>,
+    pub account_leverages_reader: Arc<MyNoSqlDataReaderTcp<AccountLeverageNoSqlEntity>>,
+    pub market_state_policy: MarketStatePolicy,
+    pub closed_positions_history: Arc<ClosedPositionsHistory>,
+    pub account_positions_pollers: AccountPositionsPollers,
+    pub invalid_settings_log: InvalidSettingsLog,
 }
 
 impl AppContext {
     pub async fn new(
         settings: Arc<crate::settings::SettingsReader>,
         service_context: &ServiceContext,
-    ) -> AppContext {
+    ) -> Result<AppContext, String> {
         let settings = settings.get_settings().await;
         let position_manager_grpc_client = Arc::new(PositionManagerGrpcClient::new(
             GrpcSettings::new_arc(settings.position_manager_grpc.to_string()),
@@ -47,9 +63,26 @@ impl AppContext {
         let trading_instruments_reader = service_context.get_ns_reader().await;
         let trading_groups_reader = service_context.get_ns_reader().await;
         let trading_profiles_reader = service_context.get_ns_reader().await;
-        let bid_ask_snapshot_ns_reader = service_context.get_ns_reader().await;
+        let bid_ask_snapshot_ns_reader: Arc<MyNoSqlDataReaderTcp<BidAskSnapshotNoSqlEntity>> =
+            service_context.get_ns_reader().await;
+        let bid_ask_cache = Arc::new(BidAskCache::new());
+        bid_ask_snapshot_ns_reader
+            .assign_callback(bid_ask_cache.clone())
+            .await;
+        let instrument_holidays_reader = service_context.get_ns_reader().await;
+        let instrument_trading_settings_reader = service_context.get_ns_reader().await;
+        let trading_profile_instrument_settings_reader = service_context.get_ns_reader().await;
+        let account_leverages_reader = service_context.get_ns_reader().await;
+        let market_state_policy = settings.market_state_policy.as_ref().into();
+        let closed_positions_history = match &settings.closed_positions_history_path {
+            Some(path) => ClosedPositionsHistory::open(
+                path,
+                settings.closed_positions_history_retention_days,
+            )?,
+            None => ClosedPositionsHistory::new_in_memory(),
+        };
 
-        AppContext {
+        Ok(AppContext {
             position_manager_grpc_client,
             accounts_manager_grpc_client,
             trading_instruments_reader,
@@ -57,7 +90,16 @@ impl AppContext {
             trading_profiles_reader,
             a_book_bridge_grpc_client,
             bid_ask_snapshot_ns_reader,
-        }
+            bid_ask_cache,
+            instrument_holidays_reader,
+            instrument_trading_settings_reader,
+            trading_profile_instrument_settings_reader,
+            account_leverages_reader,
+            market_state_policy,
+            closed_positions_history: Arc::new(closed_positions_history),
+            account_positions_pollers: AccountPositionsPollers::new(),
+            invalid_settings_log: InvalidSettingsLog::new(),
+        })
     }
 }
 
@@ -0,0 +1,90 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::{watch, Mutex};
+
+use crate::position_manager_grpc::PositionManagerActivePositionGrpcModel;
+
+pub type ActivePositionsSnapshot = Arc<Vec<PositionManagerActivePositionGrpcModel>>;
+pub type ActivePositionsSender = Arc<watch::Sender<Option<ActivePositionsSnapshot>>>;
+
+/// One Position Manager poller per account, shared by every subscriber of the account. The
+/// latest snapshot is published through a watch channel, so a slow subscriber only skips
+/// intermediate states and never holds the poller back.
+pub struct AccountPositionsPollers {
+    pollers: Mutex<HashMap<(String, String), ActivePositionsSender>>,
+}
+
+impl AccountPositionsPollers {
+    pub fn new() -> Self {
+        Self {
+            pollers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the snapshot receiver of the account. The sender is returned only when no poller
+    /// runs for the account yet, and the caller has to start one.
+    pub async fn subscribe(
+        &self,
+        trader_id: &str,
+        account_id: &str,
+    ) -> (
+        watch::Receiver<Option<ActivePositionsSnapshot>>,
+        Option<ActivePositionsSender>,
+    ) {
+        let mut pollers = self.pollers.lock().await;
+        let key = (trader_id.to_string(), account_id.to_string());
+
+        if let Some(sender) = pollers.get(&key) {
+            return (sender.subscribe(), None);
+        }
+
+        let (sender, receiver) = watch::channel(None);
+        let sender = Arc::new(sender);
+        pollers.insert(key, sender.clone());
+
+        (receiver, Some(sender))
+    }
+
+    /// Unregisters the poller once the last subscriber is gone. Runs under the same lock as
+    /// `subscribe`, so a new subscriber either sees the poller alive or starts a new one.
+    pub async fn keep_polling(&self, trader_id: &str, account_id: &str) -> bool {
+        let mut pollers = self.pollers.lock().await;
+        let key = (trader_id.to_string(), account_id.to_string());
+
+        let Some(sender) = pollers.get(&key) else {
+            return false;
+        };
+
+        if sender.receiver_count() > 0 {
+            return true;
+        }
+
+        pollers.remove(&key);
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AccountPositionsPollers;
+
+    #[tokio::test]
+    async fn share_poller_between_subscribers() {
+        let pollers = AccountPositionsPollers::new();
+
+        let (first, sender) = pollers.subscribe("trader", "account").await;
+        assert_eq!(true, sender.is_some());
+
+        let (second, sender) = pollers.subscribe("trader", "account").await;
+        assert_eq!(true, sender.is_none());
+        assert_eq!(true, pollers.keep_polling("trader", "account").await);
+
+        drop(first);
+        drop(second);
+
+        assert_eq!(false, pollers.keep_polling("trader", "account").await);
+
+        let (_, sender) = pollers.subscribe("trader", "account").await;
+        assert_eq!(true, sender.is_some());
+    }
+}
@@ -0,0 +1,119 @@
+use std::{collections::HashMap, sync::Arc};
+
+use my_nosql_contracts::BidAskSnapshotNoSqlEntity;
+use service_sdk::my_no_sql_sdk::reader::MyNoSqlDataReaderCallBacks;
+use tokio::sync::{broadcast, RwLock};
+
+use crate::{BidAskIndex, ConversionHop};
+
+const BID_ASK_UPDATES_CAPACITY: usize = 4096;
+
+pub struct BidAskCache {
+    index: RwLock<BidAskIndex<BidAskSnapshotNoSqlEntity>>,
+    updates: broadcast::Sender<Arc<BidAskSnapshotNoSqlEntity>>,
+    /// Cross rate paths by (from, to). Dropped when an instrument joins or leaves the feed.
+    conversion_paths: RwLock<HashMap<(String, String), Vec<ConversionHop>>>,
+}
+
+impl BidAskCache {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(BID_ASK_UPDATES_CAPACITY);
+
+        Self {
+            index: RwLock::new(BidAskIndex::new()),
+            updates,
+            conversion_paths: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<BidAskSnapshotNoSqlEntity>> {
+        self.updates.subscribe()
+    }
+
+    pub async fn get_by_id(&self, instrument_id: &str) -> Option<Arc<BidAskSnapshotNoSqlEntity>> {
+        self.index.read().await.get_by_id(instrument_id)
+    }
+
+    pub async fn get_by_pair(
+        &self,
+        base: &str,
+        quote: &str,
+    ) -> Option<Arc<BidAskSnapshotNoSqlEntity>> {
+        self.index.read().await.get_by_pair(base, quote)
+    }
+
+    pub async fn find_pair(
+        &self,
+        first: &str,
+        second: &str,
+    ) -> Option<Arc<BidAskSnapshotNoSqlEntity>> {
+        self.index.read().await.find_pair(first, second)
+    }
+
+    pub async fn get_all(&self) -> Vec<Arc<BidAskSnapshotNoSqlEntity>> {
+        self.index.read().await.get_all()
+    }
+
+    pub async fn get_conversion_path(&self, from: &str, to: &str) -> Option<Vec<ConversionHop>> {
+        self.conversion_paths
+            .read()
+            .await
+            .get(&(from.to_string(), to.to_string()))
+            .cloned()
+    }
+
+    pub async fn set_conversion_path(&self, from: &str, to: &str, path: Vec<ConversionHop>) {
+        self.conversion_paths
+            .write()
+            .await
+            .insert((from.to_string(), to.to_string()), path);
+    }
+
+    pub async fn remove_conversion_path(&self, from: &str, to: &str) {
+        self.conversion_paths
+            .write()
+            .await
+            .remove(&(from.to_string(), to.to_string()));
+    }
+}
+
+#[tonic::async_trait]
+impl MyNoSqlDataReaderCallBacks<BidAskSnapshotNoSqlEntity> for BidAskCache {
+    async fn inserted_or_replaced(
+        &self,
+        _partition_key: &str,
+        entities: Vec<Arc<BidAskSnapshotNoSqlEntity>>,
+    ) {
+        let mut has_new_instruments = false;
+
+        {
+            let mut index = self.index.write().await;
+
+            for entity in &entities {
+                has_new_instruments |= index.get_by_id(&entity.row_key).is_none();
+                index.insert(&entity.row_key, &entity.base, &entity.quote, entity.clone());
+            }
+        }
+
+        if has_new_instruments {
+            self.conversion_paths.write().await.clear();
+        }
+
+        for entity in entities {
+            // No subscribers is a normal state
+            let _ = self.updates.send(entity);
+        }
+    }
+
+    async fn deleted(&self, _partition_key: &str, entities: Vec<Arc<BidAskSnapshotNoSqlEntity>>) {
+        {
+            let mut index = self.index.write().await;
+
+            for entity in entities {
+                index.remove(&entity.row_key, &entity.base, &entity.quote);
+            }
+        }
+
+        self.conversion_paths.write().await.clear();
+    }
+}
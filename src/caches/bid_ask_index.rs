@@ -0,0 +1,97 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+};
+
+/// Bid-asks by instrument id and by (base, quote). Depends on std only, so
+/// `benches/open_flow_price_lookups.rs` includes this file by path.
+pub struct BidAskIndex<T> {
+    by_id: HashMap<String, Arc<T>>,
+    /// Several instruments may quote the same pair, so the pair keeps every id and resolves to
+    /// the first one still present.
+    by_pair: HashMap<(String, String), BTreeSet<String>>,
+}
+
+impl<T> BidAskIndex<T> {
+    pub fn new() -> Self {
+        Self {
+            by_id: HashMap::new(),
+            by_pair: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, id: &str, base: &str, quote: &str, item: Arc<T>) {
+        self.by_id.insert(id.to_string(), item);
+        self.by_pair
+            .entry((base.to_string(), quote.to_string()))
+            .or_default()
+            .insert(id.to_string());
+    }
+
+    pub fn remove(&mut self, id: &str, base: &str, quote: &str) {
+        self.by_id.remove(id);
+
+        let key = (base.to_string(), quote.to_string());
+        if let Some(ids) = self.by_pair.get_mut(&key) {
+            ids.remove(id);
+
+            if ids.is_empty() {
+                self.by_pair.remove(&key);
+            }
+        }
+    }
+
+    pub fn get_by_id(&self, id: &str) -> Option<Arc<T>> {
+        self.by_id.get(id).cloned()
+    }
+
+    pub fn get_by_pair(&self, base: &str, quote: &str) -> Option<Arc<T>> {
+        self.by_pair
+            .get(&(base.to_string(), quote.to_string()))?
+            .iter()
+            .find_map(|id| self.by_id.get(id).cloned())
+    }
+
+    /// Looks up a pair in either direction: base/quote first, then quote/base.
+    pub fn find_pair(&self, first: &str, second: &str) -> Option<Arc<T>> {
+        self.get_by_pair(first, second)
+            .or_else(|| self.get_by_pair(second, first))
+    }
+
+    pub fn get_all(&self) -> Vec<Arc<T>> {
+        self.by_id.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::BidAskIndex;
+
+    #[test]
+    fn find_pair_in_both_directions() {
+        let mut index = BidAskIndex::new();
+        index.insert("EURUSD", "EUR", "USD", Arc::new(1));
+
+        assert_eq!(Some(1), index.find_pair("EUR", "USD").map(|x| *x));
+        assert_eq!(Some(1), index.find_pair("USD", "EUR").map(|x| *x));
+        assert_eq!(None, index.find_pair("USD", "JPY").map(|x| *x));
+    }
+
+    #[test]
+    fn pair_quoted_by_several_instruments() {
+        let mut index = BidAskIndex::new();
+        index.insert("EURUSD", "EUR", "USD", Arc::new(1));
+        index.insert("EURUSD.m", "EUR", "USD", Arc::new(2));
+
+        assert_eq!(Some(1), index.get_by_pair("EUR", "USD").map(|x| *x));
+
+        index.remove("EURUSD", "EUR", "USD");
+        assert_eq!(Some(2), index.get_by_pair("EUR", "USD").map(|x| *x));
+        assert_eq!(Some(2), index.get_by_id("EURUSD.m").map(|x| *x));
+
+        index.remove("EURUSD.m", "EUR", "USD");
+        assert_eq!(None, index.get_by_pair("EUR", "USD").map(|x| *x));
+    }
+}
@@ -0,0 +1,256 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    sync::{Arc, Mutex},
+};
+
+use prost::Message;
+use tokio::sync::RwLock;
+
+use crate::trading_executor_grpc::TradingExecutorClosedPositionHistoryGrpcModel;
+
+#[derive(Debug, Clone, Default)]
+pub struct ClosedPositionsFilter {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub asset_pair: Option<String>,
+}
+
+impl ClosedPositionsFilter {
+    pub fn is_matched(&self, record: &TradingExecutorClosedPositionHistoryGrpcModel) -> bool {
+        if let Some(from) = self.from {
+            if record.close_date_unix_timestamp_milliseconds < from {
+                return false;
+            }
+        }
+
+        if let Some(to) = self.to {
+            if record.close_date_unix_timestamp_milliseconds > to {
+                return false;
+            }
+        }
+
+        if let Some(asset_pair) = &self.asset_pair {
+            let Some(position) = &record.position else {
+                return false;
+            };
+
+            if &position.asset_pair != asset_pair {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Closed positions indexed by account. Records are appended to a file as length-delimited
+/// protobuf messages and loaded back on start; without a file the history lives in memory only.
+/// Records older than the retention are dropped from the file on start.
+pub struct ClosedPositionsHistory {
+    file: Option<Arc<Mutex<File>>>,
+    index: RwLock<HashMap<String, Vec<TradingExecutorClosedPositionHistoryGrpcModel>>>,
+}
+
+impl ClosedPositionsHistory {
+    pub fn new_in_memory() -> Self {
+        Self {
+            file: None,
+            index: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn open(path: &str, retention_days: Option<u32>) -> Result<Self, String> {
+        let open_error =
+            |err: std::io::Error| format!("Can't open closed positions history {}: {}", path, err);
+
+        let mut content = vec![];
+        match File::open(path) {
+            Ok(mut file) => {
+                file.read_to_end(&mut content).map_err(open_error)?;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(open_error(err)),
+        }
+
+        let (mut records, valid_len) = decode_records(&content);
+        let loaded_count = records.len();
+
+        if let Some(retention_days) = retention_days {
+            let retention_ms = retention_days as u64 * 24 * 60 * 60 * 1000;
+            let min_close_date =
+                (chrono::Utc::now().timestamp_millis() as u64).saturating_sub(retention_ms);
+            records.retain(|x| x.close_date_unix_timestamp_milliseconds >= min_close_date);
+        }
+
+        if valid_len < content.len() || records.len() < loaded_count {
+            // Rewritten next to the original and renamed over it, so a crash keeps the old file.
+            let compacted_path = format!("{}.compacted", path);
+            let mut compacted = File::create(&compacted_path).map_err(open_error)?;
+
+            for record in &records {
+                compacted
+                    .write_all(&record.encode_length_delimited_to_vec())
+                    .map_err(open_error)?;
+            }
+
+            compacted.sync_all().map_err(open_error)?;
+            std::fs::rename(&compacted_path, path).map_err(open_error)?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(open_error)?;
+
+        let mut index: HashMap<String, Vec<TradingExecutorClosedPositionHistoryGrpcModel>> =
+            HashMap::new();
+
+        for record in records {
+            index
+                .entry(record.account_id.clone())
+                .or_default()
+                .push(record);
+        }
+
+        Ok(Self {
+            file: Some(Arc::new(Mutex::new(file))),
+            index: RwLock::new(index),
+        })
+    }
+
+    /// The record is on disk before it shows up in the index. The write runs on the blocking
+    /// pool and doesn't hold the index lock.
+    pub async fn add(
+        &self,
+        record: TradingExecutorClosedPositionHistoryGrpcModel,
+    ) -> std::io::Result<()> {
+        if let Some(file) = &self.file {
+            let file = file.clone();
+            let content = record.encode_length_delimited_to_vec();
+
+            tokio::task::spawn_blocking(move || {
+                let mut file = file.lock().map_err(|_| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        "History file lock is poisoned by a failed write",
+                    )
+                })?;
+                file.write_all(&content)?;
+                file.sync_data()
+            })
+            .await??;
+        }
+
+        self.index
+            .write()
+            .await
+            .entry(record.account_id.clone())
+            .or_default()
+            .push(record);
+
+        Ok(())
+    }
+
+    pub async fn get(
+        &self,
+        trader_id: &str,
+        account_id: &str,
+        filter: &ClosedPositionsFilter,
+    ) -> Vec<TradingExecutorClosedPositionHistoryGrpcModel> {
+        let index = self.index.read().await;
+
+        let Some(records) = index.get(account_id) else {
+            return vec![];
+        };
+
+        records
+            .iter()
+            .filter(|x| x.trader_id == trader_id && filter.is_matched(x))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Returns decoded records and the length of the valid prefix. A crash in the middle of a
+/// write leaves a partial record at the end, which is where decoding stops.
+pub fn decode_records(
+    content: &[u8],
+) -> (Vec<TradingExecutorClosedPositionHistoryGrpcModel>, usize) {
+    let mut records = vec![];
+    let mut remaining = content;
+    let mut valid_len = 0;
+
+    while !remaining.is_empty() {
+        match TradingExecutorClosedPositionHistoryGrpcModel::decode_length_delimited(&mut remaining)
+        {
+            Ok(record) => {
+                records.push(record);
+                valid_len = content.len() - remaining.len();
+            }
+            Err(_) => break,
+        }
+    }
+
+    (records, valid_len)
+}
+
+#[cfg(test)]
+mod test {
+    use prost::Message;
+
+    use crate::trading_executor_grpc::{
+        TradingExecutorClosedPositionGrpcModel, TradingExecutorClosedPositionHistoryGrpcModel,
+    };
+
+    use super::{decode_records, ClosedPositionsFilter};
+
+    fn record(
+        id: &str,
+        asset_pair: &str,
+        close_date: u64,
+    ) -> TradingExecutorClosedPositionHistoryGrpcModel {
+        TradingExecutorClosedPositionHistoryGrpcModel {
+            trader_id: "trader".to_string(),
+            account_id: "account".to_string(),
+            close_date_unix_timestamp_milliseconds: close_date,
+            commission: 0.0,
+            position: Some(TradingExecutorClosedPositionGrpcModel {
+                id: id.to_string(),
+                asset_pair: asset_pair.to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn decode_records_with_truncated_tail() {
+        let mut content = vec![];
+        content.extend(record("1", "EURUSD", 100).encode_length_delimited_to_vec());
+        content.extend(record("2", "EURUSD", 200).encode_length_delimited_to_vec());
+        let valid_len = content.len();
+
+        let partial = record("3", "EURUSD", 300).encode_length_delimited_to_vec();
+        content.extend(&partial[..partial.len() / 2]);
+
+        let (records, len) = decode_records(&content);
+
+        assert_eq!(2, records.len());
+        assert_eq!(valid_len, len);
+    }
+
+    #[test]
+    fn filter_by_date_range_and_instrument() {
+        let filter = ClosedPositionsFilter {
+            from: Some(100),
+            to: Some(200),
+            asset_pair: Some("EURUSD".to_string()),
+        };
+
+        assert_eq!(true, filter.is_matched(&record("1", "EURUSD", 150)));
+        assert_eq!(false, filter.is_matched(&record("2", "XAUUSD", 150)));
+        assert_eq!(false, filter.is_matched(&record("3", "EURUSD", 250)));
+    }
+}
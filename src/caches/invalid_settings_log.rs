@@ -0,0 +1,46 @@
+use std::{collections::HashSet, sync::Mutex};
+
+use service_sdk::my_logger::{LogEventCtx, LOGGER};
+
+/// Invalid settings rows are read on every request. Each row is logged once per process, so
+/// a broken row doesn't flood the log.
+pub struct InvalidSettingsLog {
+    reported: Mutex<HashSet<String>>,
+}
+
+impl InvalidSettingsLog {
+    pub fn new() -> Self {
+        Self {
+            reported: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn report(&self, process: &'static str, key: String, message: String) {
+        if !self.is_first_report(&key) {
+            return;
+        }
+
+        LOGGER.write_warning(process, message, Some(LogEventCtx::new().add("key", key)));
+    }
+
+    fn is_first_report(&self, key: &str) -> bool {
+        // The set stays consistent if a holder panicked, so a poisoned lock is still usable
+        let mut reported = self.reported.lock().unwrap_or_else(|err| err.into_inner());
+
+        reported.insert(key.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InvalidSettingsLog;
+
+    #[test]
+    fn each_key_is_reported_once() {
+        let log = InvalidSettingsLog::new();
+
+        assert_eq!(true, log.is_first_report("holiday/EURUSD/1"));
+        assert_eq!(false, log.is_first_report("holiday/EURUSD/1"));
+        assert_eq!(true, log.is_first_report("holiday/EURUSD/2"));
+    }
+}
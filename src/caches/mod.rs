@@ -0,0 +1,11 @@
+mod account_positions_pollers;
+mod bid_ask_cache;
+mod bid_ask_index;
+mod closed_positions_history;
+mod invalid_settings_log;
+
+pub use account_positions_pollers::*;
+pub use bid_ask_cache::*;
+pub use bid_ask_index::*;
+pub use closed_positions_history::*;
+pub use invalid_settings_log::*;
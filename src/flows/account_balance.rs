@@ -0,0 +1,72 @@
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{
+    accounts_manager_grpc::{
+        AccountManagerUpdateAccountBalanceGrpcRequest, AccountsManagerOperationResult,
+        UpdateBalanceReason,
+    },
+    AppContext, DownstreamContext, DownstreamService, TradingExecutorError,
+};
+
+pub async fn update_account_balance(
+    app: &AppContext,
+    trader_id: &str,
+    account_id: &str,
+    process_id: String,
+    delta: f64,
+    reason: UpdateBalanceReason,
+    comment: &str,
+    allow_negative_balance: bool,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<(), TradingExecutorError> {
+    let balance_update_request = AccountManagerUpdateAccountBalanceGrpcRequest {
+        trader_id: trader_id.to_string(),
+        account_id: account_id.to_string(),
+        delta,
+        comment: comment.to_string(),
+        process_id,
+        allow_negative_balance,
+        reason: reason as i32,
+        reference_transaction_id: None,
+    };
+
+    let downstream = DownstreamContext::new(
+        trader_id,
+        account_id,
+        &balance_update_request.process_id,
+        telemetry_context,
+    );
+
+    let balance_update_result = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "update_client_account_balance",
+            app.accounts_manager_grpc_client
+                .update_client_account_balance(
+                    balance_update_request.clone(),
+                    &MyTelemetryContext::new(),
+                ),
+        )
+        .await?;
+
+    trade_log::trade_log!(
+        trader_id,
+        account_id,
+        &balance_update_request.process_id,
+        "n/a",
+        comment,
+        telemetry_context.clone(),
+        "request" = &balance_update_request,
+        "response" = &balance_update_result,
+        "trace_id" = &telemetry_context.as_string()
+    );
+
+    if AccountsManagerOperationResult::Ok as i32 != balance_update_result.result {
+        return Err(TradingExecutorError::from_accounts_manager_code(
+            balance_update_result.result,
+            &downstream,
+        ));
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,198 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use my_nosql_contracts::TradingGroupNoSqlEntity;
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{
+    accounts_manager_grpc::AccountManagerGetClientAccountGrpcRequest,
+    calculate_account_margin,
+    position_manager_grpc::{
+        PositionManagerGetPendingPositionsGrpcRequest, PositionManagerPendingPositionGrpcModel,
+        PositionManagerPositionSide,
+    },
+    trading_executor_grpc::{
+        TradingExecutorAccountSummaryGrpcModel, TradingExecutorGetAccountSummaryGrpcRequest,
+        TradingExecutorInstrumentExposureGrpcModel,
+    },
+    AppContext, DownstreamContext, DownstreamService, RevaluedPosition, TradingExecutorError,
+};
+
+pub async fn get_account_summary(
+    app: &Arc<AppContext>,
+    request: TradingExecutorGetAccountSummaryGrpcRequest,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<TradingExecutorAccountSummaryGrpcModel, TradingExecutorError> {
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        "n/a",
+        telemetry_context,
+    );
+
+    let Some(account) = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "get_client_account",
+            app.accounts_manager_grpc_client.get_client_account(
+                AccountManagerGetClientAccountGrpcRequest {
+                    trader_id: request.trader_id.clone(),
+                    account_id: request.account_id.clone(),
+                },
+                telemetry_context,
+            ),
+        )
+        .await?
+        .account
+    else {
+        return Err(TradingExecutorError::AccountNotFound);
+    };
+
+    let Some(trading_group) = app
+        .trading_groups_reader
+        .get_entity(
+            TradingGroupNoSqlEntity::generate_partition_key(),
+            &account.trading_group,
+        )
+        .await
+    else {
+        return Err(TradingExecutorError::TradingGroupNotFound);
+    };
+
+    let (margin, active_positions) = calculate_account_margin(
+        app,
+        &account,
+        &trading_group.trading_profile_id,
+        telemetry_context,
+    )
+    .await?;
+
+    let pending_positions = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "get_account_pending_positions",
+            app.position_manager_grpc_client
+                .get_account_pending_positions(
+                    PositionManagerGetPendingPositionsGrpcRequest {
+                        trader_id: request.trader_id.clone(),
+                        account_id: request.account_id.clone(),
+                    },
+                    telemetry_context,
+                ),
+        )
+        .await?
+        .unwrap_or_default();
+
+    Ok(TradingExecutorAccountSummaryGrpcModel {
+        account_id: account.id,
+        currency: account.currency,
+        balance: margin.balance,
+        equity: margin.equity,
+        floating_pnl: margin.floating_pnl,
+        used_margin: margin.used_margin,
+        free_margin: margin.free_margin,
+        margin_level: margin.margin_level,
+        active_positions_count: active_positions.len() as i32,
+        pending_positions_count: pending_positions.len() as i32,
+        exposures: aggregate_exposure(&active_positions, &pending_positions),
+    })
+}
+
+pub fn aggregate_exposure(
+    active_positions: &[RevaluedPosition],
+    pending_positions: &[PositionManagerPendingPositionGrpcModel],
+) -> Vec<TradingExecutorInstrumentExposureGrpcModel> {
+    let mut result: BTreeMap<String, TradingExecutorInstrumentExposureGrpcModel> = BTreeMap::new();
+
+    for active_position in active_positions {
+        let position = &active_position.position;
+        let exposure = result
+            .entry(position.asset_pair.clone())
+            .or_insert_with(|| TradingExecutorInstrumentExposureGrpcModel {
+                asset_pair: position.asset_pair.clone(),
+                ..Default::default()
+            });
+
+        let notional = position.invest_amount * position.leverage;
+
+        match position.side() {
+            PositionManagerPositionSide::Buy => exposure.buy_notional += notional,
+            PositionManagerPositionSide::Sell => exposure.sell_notional += notional,
+        }
+
+        exposure.floating_pnl += active_position.pnl;
+    }
+
+    for position in pending_positions {
+        let exposure = result
+            .entry(position.asset_pair.clone())
+            .or_insert_with(|| TradingExecutorInstrumentExposureGrpcModel {
+                asset_pair: position.asset_pair.clone(),
+                ..Default::default()
+            });
+
+        exposure.pending_notional += position.invest_amount * position.leverage;
+    }
+
+    result
+        .into_values()
+        .map(|mut exposure| {
+            exposure.net_notional = exposure.buy_notional - exposure.sell_notional;
+            exposure
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        position_manager_grpc::{
+            PositionManagerActivePositionGrpcModel, PositionManagerPendingPositionGrpcModel,
+            PositionManagerPositionSide,
+        },
+        RevaluedPosition,
+    };
+
+    use super::aggregate_exposure;
+
+    fn active_position(
+        asset_pair: &str,
+        side: PositionManagerPositionSide,
+        invest_amount: f64,
+        pnl: f64,
+    ) -> RevaluedPosition {
+        RevaluedPosition {
+            position: PositionManagerActivePositionGrpcModel {
+                asset_pair: asset_pair.to_string(),
+                side: side as i32,
+                invest_amount,
+                leverage: 10.0,
+                ..Default::default()
+            },
+            pnl,
+        }
+    }
+
+    #[test]
+    fn aggregate_exposure_by_instrument() {
+        let active_positions = vec![
+            active_position("EURUSD", PositionManagerPositionSide::Buy, 100.0, 5.0),
+            active_position("EURUSD", PositionManagerPositionSide::Sell, 30.0, -1.0),
+            active_position("XAUUSD", PositionManagerPositionSide::Sell, 50.0, 2.0),
+        ];
+        let pending_positions = vec![PositionManagerPendingPositionGrpcModel {
+            asset_pair: "XAUUSD".to_string(),
+            invest_amount: 10.0,
+            leverage: 5.0,
+            ..Default::default()
+        }];
+
+        let exposure = aggregate_exposure(&active_positions, &pending_positions);
+
+        assert_eq!(2, exposure.len());
+        assert_eq!("EURUSD", exposure[0].asset_pair);
+        assert!((exposure[0].net_notional - 700.0).abs() < 1e-9);
+        assert!((exposure[0].floating_pnl - 4.0).abs() < 1e-9);
+        assert!((exposure[1].sell_notional - 500.0).abs() < 1e-9);
+        assert!((exposure[1].pending_notional - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,142 @@
+use std::{collections::HashMap, sync::Arc};
+
+use service_sdk::my_telemetry::MyTelemetryContext;
+use tokio::{
+    sync::{mpsc, Semaphore},
+    task::JoinSet,
+};
+
+use crate::{
+    cancel_pending,
+    position_manager_grpc::PositionManagerGetPendingPositionsGrpcRequest,
+    trading_executor_grpc::{
+        TradingExecutorCancelAllPendingGrpcRequest, TradingExecutorCancelAllPendingResultGrpcModel,
+        TradingExecutorCancelPendingGrpcRequest, TradingExecutorOperationsCodes,
+        TradingExecutorPendingPositionGrpcModel,
+    },
+    AppContext, DownstreamContext, DownstreamService, TradingExecutorError,
+};
+
+const CANCEL_ALL_PENDING_MAX_CONCURRENCY: usize = 10;
+const CANCEL_ALL_PENDING_STREAM_BUFFER: usize = 1024;
+
+pub async fn cancel_all_pending(
+    app: &Arc<AppContext>,
+    request: TradingExecutorCancelAllPendingGrpcRequest,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<
+    mpsc::Receiver<Result<TradingExecutorCancelAllPendingResultGrpcModel, tonic::Status>>,
+    TradingExecutorError,
+> {
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        "n/a",
+        telemetry_context,
+    );
+
+    let pending_positions = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "get_account_pending_positions",
+            app.position_manager_grpc_client
+                .get_account_pending_positions(
+                    PositionManagerGetPendingPositionsGrpcRequest {
+                        trader_id: request.trader_id.clone(),
+                        account_id: request.account_id.clone(),
+                    },
+                    telemetry_context,
+                ),
+        )
+        .await?;
+
+    let (sender, receiver) = mpsc::channel(CANCEL_ALL_PENDING_STREAM_BUFFER);
+
+    let Some(pending_positions) = pending_positions else {
+        return Ok(receiver);
+    };
+
+    let semaphore = Arc::new(Semaphore::new(CANCEL_ALL_PENDING_MAX_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    let mut task_positions = HashMap::new();
+
+    for position in pending_positions {
+        if let Some(asset_pair) = &request.asset_pair {
+            if &position.asset_pair != asset_pair {
+                continue;
+            }
+        }
+
+        if let Some(side) = request.side {
+            if position.side != side {
+                continue;
+            }
+        }
+
+        let app = app.clone();
+        let semaphore = semaphore.clone();
+        let telemetry_context = telemetry_context.clone();
+        let cancel_request = TradingExecutorCancelPendingGrpcRequest {
+            account_id: position.account_id,
+            trader_id: position.trader_id,
+            position_id: position.id,
+        };
+
+        let position_id = cancel_request.position_id.clone();
+
+        let task = tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.unwrap();
+            let position_id = cancel_request.position_id.clone();
+            let result = cancel_pending(&app, cancel_request, &telemetry_context).await;
+
+            into_cancel_result(position_id, result)
+        });
+
+        task_positions.insert(task.id(), position_id);
+    }
+
+    // Results are streamed as soon as each cancel finishes, so a slow position doesn't hold
+    // back the ones that are already done.
+    tokio::spawn(async move {
+        while let Some(task_result) = tasks.join_next_with_id().await {
+            let cancel_result = match task_result {
+                Ok((_, cancel_result)) => cancel_result,
+                Err(err) => {
+                    let position_id = task_positions.remove(&err.id()).unwrap_or_default();
+                    into_cancel_result(position_id, Err(TradingExecutorError::TechError))
+                }
+            };
+
+            if sender.send(Ok(cancel_result)).await.is_err() {
+                tasks.abort_all();
+                return;
+            }
+        }
+    });
+
+    return Ok(receiver);
+}
+
+fn into_cancel_result(
+    position_id: String,
+    result: Result<TradingExecutorPendingPositionGrpcModel, TradingExecutorError>,
+) -> TradingExecutorCancelAllPendingResultGrpcModel {
+    match result {
+        Ok(position) => TradingExecutorCancelAllPendingResultGrpcModel {
+            position_id,
+            status: TradingExecutorOperationsCodes::Ok as i32,
+            reject_reason: None,
+            position: Some(position),
+        },
+        Err(error) => {
+            let reject_reason = Some(error.get_reject_reason().into());
+            let error: TradingExecutorOperationsCodes = error.into();
+            TradingExecutorCancelAllPendingResultGrpcModel {
+                position_id,
+                status: error.into(),
+                reject_reason,
+                position: None,
+            }
+        }
+    }
+}
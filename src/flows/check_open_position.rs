@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{
+    trading_executor_grpc::{TradingExecutorOpenPositionGrpcRequest, TradingExecutorPositionSide},
+    validate_open_position_request, validate_open_position_requirements, AppContext,
+    DownstreamContext, ExecutableBidAsk, OpenPositionChecks, TradingExecutorError,
+};
+
+/// Result of a dry-run open. Checks that can't run without a missing entity or invest amount are
+/// skipped, so an empty `failed_checks` means the open would pass validation with the current
+/// state.
+#[derive(Debug, Default)]
+pub struct OpenPositionCheck {
+    pub failed_checks: Vec<TradingExecutorError>,
+    /// Invest amount derived from the requested sizing mode.
+    pub invest_amount: Option<f64>,
+    pub required_margin: Option<f64>,
+    pub commission: Option<f64>,
+    pub bid_ask: Option<ExecutableBidAsk>,
+    pub indicative_price: Option<f64>,
+}
+
+impl OpenPositionCheck {
+    pub fn add_failure(&mut self, error: TradingExecutorError) {
+        let rule_id = error.get_reject_reason().rule_id;
+
+        if self
+            .failed_checks
+            .iter()
+            .any(|x| x.get_reject_reason().rule_id == rule_id)
+        {
+            return;
+        }
+
+        self.failed_checks.push(error);
+    }
+}
+
+impl OpenPositionChecks for OpenPositionCheck {
+    fn check(
+        &mut self,
+        result: Result<(), TradingExecutorError>,
+    ) -> Result<(), TradingExecutorError> {
+        if let Err(error) = result {
+            self.add_failure(error);
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the open position validation pipeline without the execution delay, hedging, charging
+/// or opening the position. The checks are the ones `open_position` runs.
+pub async fn check_open_position(
+    app: &Arc<AppContext>,
+    request: &TradingExecutorOpenPositionGrpcRequest,
+    telemetry_context: &MyTelemetryContext,
+) -> OpenPositionCheck {
+    let mut result = OpenPositionCheck::default();
+
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        telemetry_context,
+    );
+
+    let validated =
+        match validate_open_position_request(app, request, &downstream, &mut result).await {
+            Ok(validated) => validated,
+            Err(error) => {
+                result.add_failure(error);
+                return result;
+            }
+        };
+
+    result.invest_amount = validated.invest_amount;
+
+    let requirements = validate_open_position_requirements(
+        app,
+        request,
+        &validated,
+        &mut result,
+        telemetry_context,
+    )
+    .await;
+
+    let requirements = match requirements {
+        Ok(requirements) => requirements,
+        Err(error) => {
+            result.add_failure(error);
+            return result;
+        }
+    };
+
+    if let Some(bid_ask) = requirements.bid_ask {
+        let is_buy = request.side() == TradingExecutorPositionSide::Buy;
+        result.indicative_price = Some(bid_ask.get_open_price(is_buy));
+        result.bid_ask = Some(bid_ask);
+    }
+
+    result.commission = requirements.commission;
+
+    if let (Some(invest_amount), Some(commission)) =
+        (validated.invest_amount, requirements.commission)
+    {
+        result.required_margin = Some(invest_amount + commission);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::TradingExecutorError;
+
+    use super::OpenPositionCheck;
+
+    #[test]
+    fn failed_checks_are_not_duplicated() {
+        let mut check = OpenPositionCheck::default();
+
+        check.add_failure(TradingExecutorError::NoLiquidity);
+        check.add_failure(TradingExecutorError::DayOff);
+        check.add_failure(TradingExecutorError::NoLiquidity);
+
+        assert_eq!(2, check.failed_checks.len());
+    }
+}
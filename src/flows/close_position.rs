@@ -1,15 +1,20 @@
 use std::sync::Arc;
 
-use my_nosql_contracts::TradingInstrumentNoSqlEntity;
+use my_nosql_contracts::{TradingGroupNoSqlEntity, TradingInstrumentNoSqlEntity};
 
 use crate::{
+    accounts_manager_grpc::UpdateBalanceReason,
+    calculate_commission, charge_commission, get_close_markup_cost, get_markup_settings,
     position_manager_grpc::{
-        PositionManagerClosePositionGrpcRequest, PositionManagerGetActivePositionGrpcRequest,
+        PositionManagerActivePositionGrpcModel, PositionManagerClosePositionGrpcRequest,
+        PositionManagerGetActivePositionGrpcRequest, PositionManagerPositionSide,
     },
+    record_closed_position, resolve_cross_rate,
     trading_executor_grpc::{
         TradingExecutorClosePositionGrpcRequest, TradingExecutorClosedPositionGrpcModel,
     },
-    validate_instrument_day_off, validate_timeout, AppContext, TradingExecutorError,
+    update_account_balance, validate_market_state, AppContext, DownstreamContext,
+    DownstreamService, TradingExecutorError, TradingOperationType,
 };
 use service_sdk::my_telemetry;
 
@@ -17,35 +22,46 @@ pub async fn close_position(
     app: &Arc<AppContext>,
     request: TradingExecutorClosePositionGrpcRequest,
     telemetry_context: &my_telemetry::MyTelemetryContext,
-) -> Result<TradingExecutorClosedPositionGrpcModel, TradingExecutorError> {
-    let Some(account) = app
-        .accounts_manager_grpc_client
-        .get_client_account(
-            crate::accounts_manager_grpc::AccountManagerGetClientAccountGrpcRequest {
-                trader_id: request.trader_id.clone(),
-                account_id: request.account_id.clone(),
-            },
-            &telemetry_context,
+) -> Result<(TradingExecutorClosedPositionGrpcModel, f64), TradingExecutorError> {
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        telemetry_context,
+    );
+
+    let Some(account) = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "get_client_account",
+            app.accounts_manager_grpc_client.get_client_account(
+                crate::accounts_manager_grpc::AccountManagerGetClientAccountGrpcRequest {
+                    trader_id: request.trader_id.clone(),
+                    account_id: request.account_id.clone(),
+                },
+                &telemetry_context,
+            ),
         )
-        .await
-        .unwrap()
+        .await?
         .account
     else {
         return Err(TradingExecutorError::AccountNotFound);
     };
 
-    let Some(target_position) = app
-        .position_manager_grpc_client
-        .get_active_position(
-            PositionManagerGetActivePositionGrpcRequest {
-                trader_id: request.trader_id.clone(),
-                account_id: request.account_id.clone(),
-                position_id: request.position_id.clone(),
-            },
-            telemetry_context,
+    let Some(target_position) = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "get_active_position",
+            app.position_manager_grpc_client.get_active_position(
+                PositionManagerGetActivePositionGrpcRequest {
+                    trader_id: request.trader_id.clone(),
+                    account_id: request.account_id.clone(),
+                    position_id: request.position_id.clone(),
+                },
+                telemetry_context,
+            ),
         )
-        .await
-        .unwrap()
+        .await?
         .position
     else {
         return Err(TradingExecutorError::PositionNotFound);
@@ -63,50 +79,206 @@ pub async fn close_position(
         return Err(TradingExecutorError::InstrumentNotFound);
     };
 
-    let position_to_close = app
-        .position_manager_grpc_client
-        .get_active_position(
-            PositionManagerGetActivePositionGrpcRequest {
-                trader_id: request.trader_id.clone(),
-                account_id: request.account_id.clone(),
-                position_id: request.position_id.clone(),
-            },
-            telemetry_context,
+    let position_to_close = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "get_active_position",
+            app.position_manager_grpc_client.get_active_position(
+                PositionManagerGetActivePositionGrpcRequest {
+                    trader_id: request.trader_id.clone(),
+                    account_id: request.account_id.clone(),
+                    position_id: request.position_id.clone(),
+                },
+                telemetry_context,
+            ),
         )
-        .await
-        .unwrap();
+        .await?;
 
     let Some(_position_to_close) = position_to_close.position else {
         return Err(TradingExecutorError::PositionNotFound);
     };
 
-    validate_instrument_day_off(&target_instrument)?;
-    validate_timeout(
+    validate_market_state(
         app,
+        &downstream,
+        TradingOperationType::Close,
         &target_instrument,
-        &target_instrument.base,
-        &target_instrument.quote,
         &account.currency,
     )
     .await?;
 
-    let close_result = app
-        .position_manager_grpc_client
-        .close_position(
-            PositionManagerClosePositionGrpcRequest {
-                position_id: request.position_id,
-                process_id: request.process_id,
-                account_id: request.account_id,
-                trader_id: request.trader_id,
-            },
-            telemetry_context,
+    let Some(target_trading_group) = app
+        .trading_groups_reader
+        .get_entity(
+            TradingGroupNoSqlEntity::generate_partition_key(),
+            &account.trading_group,
         )
-        .await;
+        .await
+    else {
+        return Err(TradingExecutorError::TradingGroupNotFound);
+    };
+
+    let commission = calculate_commission(
+        app,
+        &target_trading_group.trading_profile_id,
+        &target_instrument,
+        &account.currency,
+        target_position.invest_amount * target_position.leverage,
+    )
+    .await?;
+    let trader_id = request.trader_id.clone();
+    let account_id = request.account_id.clone();
+    let commission_process_id = format!("{}-commission", request.process_id);
+    let markup_process_id = format!("{}-markup", request.process_id);
+
+    let close_result = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "close_position",
+            app.position_manager_grpc_client.close_position(
+                PositionManagerClosePositionGrpcRequest {
+                    position_id: request.position_id.clone(),
+                    process_id: request.process_id.clone(),
+                    account_id: request.account_id.clone(),
+                    trader_id: request.trader_id.clone(),
+                },
+                telemetry_context,
+            ),
+        )
+        .await?;
 
-    let close_result = close_result.unwrap();
     if let Some(position) = close_result.position {
-        return Ok(position.into());
+        let mut position: TradingExecutorClosedPositionGrpcModel = position.into();
+
+        let markup_result = settle_close_markup(
+            app,
+            &downstream,
+            markup_process_id.clone(),
+            &target_trading_group.trading_profile_id,
+            &target_position,
+            &mut position,
+        )
+        .await;
+
+        if let Err(err) = markup_result {
+            trade_log::trade_log!(
+                &trader_id,
+                &account_id,
+                &markup_process_id,
+                "n/a",
+                "Failed to settle close position markup.",
+                telemetry_context.clone(),
+                "position_id" = &request.position_id,
+                "err" = &format!("{:?}", err),
+                "trace_id" = &telemetry_context.as_string()
+            );
+        }
+
+        if commission > 0.0 {
+            // The position is closed already, so a rejected charge must not fail the close
+            let commission_result = charge_commission(
+                app,
+                &trader_id,
+                &account_id,
+                commission_process_id.clone(),
+                -commission,
+                "Close position commission",
+                true,
+                telemetry_context,
+            )
+            .await;
+
+            if let Err(err) = commission_result {
+                trade_log::trade_log!(
+                    &trader_id,
+                    &account_id,
+                    &commission_process_id,
+                    "n/a",
+                    "Failed to charge close position commission.",
+                    telemetry_context.clone(),
+                    "position_id" = &request.position_id,
+                    "commission" = &commission.to_string(),
+                    "err" = &format!("{:?}", err),
+                    "trace_id" = &telemetry_context.as_string()
+                );
+            }
+        }
+
+        record_closed_position(app, &downstream, &position, commission).await;
+
+        return Ok((position, commission));
+    };
+
+    return Err(TradingExecutorError::from_position_manager_code(
+        close_result.status,
+        &downstream,
+    ));
+}
+
+/// The Position Manager closes at the raw feed price and its contract, synced from proto-files,
+/// has no close price field. The profile markup on the close side is settled with a balance
+/// update instead, and the returned position shows the executable close price.
+async fn settle_close_markup(
+    app: &AppContext,
+    downstream: &DownstreamContext<'_>,
+    process_id: String,
+    trading_profile_id: &str,
+    active_position: &PositionManagerActivePositionGrpcModel,
+    position: &mut TradingExecutorClosedPositionGrpcModel,
+) -> Result<(), TradingExecutorError> {
+    let Some(markup_settings) =
+        get_markup_settings(app, trading_profile_id, &position.asset_pair).await
+    else {
+        return Ok(());
+    };
+
+    let Some(close_bid_ask) = &position.close_bid_ask else {
+        return Ok(());
+    };
+
+    if active_position.base_collateral_open_price <= 0.0 {
+        return Err(TradingExecutorError::TechError);
+    }
+
+    let (bid, ask) = markup_settings.apply(close_bid_ask.bid, close_bid_ask.ask);
+    let is_buy = active_position.side() == PositionManagerPositionSide::Buy;
+    let close_price = match is_buy {
+        true => bid,
+        false => ask,
     };
 
-    return Err(TradingExecutorError::from(close_result.status));
+    let units = active_position.invest_amount * active_position.leverage
+        / active_position.base_collateral_open_price;
+    let cost = get_close_markup_cost(is_buy, units, position.close_price, close_price);
+
+    if cost == 0.0 {
+        return Ok(());
+    }
+
+    let quote_collateral =
+        resolve_cross_rate(app, &active_position.quote, &active_position.collateral).await?;
+    let cost = cost * quote_collateral.rate;
+
+    update_account_balance(
+        app,
+        downstream.trader_id,
+        downstream.account_id,
+        process_id,
+        -cost,
+        UpdateBalanceReason::TradingResult,
+        "Close position markup",
+        true,
+        downstream.telemetry_context,
+    )
+    .await?;
+
+    if let Some(close_bid_ask) = &mut position.close_bid_ask {
+        close_bid_ask.bid = bid;
+        close_bid_ask.ask = ask;
+    }
+
+    position.close_price = close_price;
+    position.profit -= cost;
+
+    Ok(())
 }
@@ -0,0 +1,123 @@
+use crate::{
+    trading_executor_grpc::{
+        TradingExecutorClosedPositionGrpcModel, TradingExecutorClosedPositionHistoryGrpcModel,
+        TradingExecutorClosedPositionsSummaryGrpcModel,
+        TradingExecutorGetAccountClosedPositionsGrpcRequest,
+    },
+    AppContext, ClosedPositionsFilter, DownstreamContext,
+};
+
+/// The position is closed already when this is called, so a failed write is only trade logged.
+pub async fn record_closed_position(
+    app: &AppContext,
+    downstream: &DownstreamContext<'_>,
+    position: &TradingExecutorClosedPositionGrpcModel,
+    commission: f64,
+) {
+    let record = TradingExecutorClosedPositionHistoryGrpcModel {
+        trader_id: downstream.trader_id.to_string(),
+        account_id: downstream.account_id.to_string(),
+        close_date_unix_timestamp_milliseconds: chrono::Utc::now().timestamp_millis() as u64,
+        commission,
+        position: Some(position.clone()),
+    };
+
+    if let Err(err) = app.closed_positions_history.add(record).await {
+        trade_log::trade_log!(
+            downstream.trader_id,
+            downstream.account_id,
+            downstream.process_id,
+            "n/a",
+            "Failed to write closed position to history.",
+            downstream.telemetry_context.clone(),
+            "position_id" = &position.id,
+            "err" = &format!("{:?}", err),
+            "trace_id" = &downstream.telemetry_context.as_string()
+        );
+    }
+}
+
+pub async fn get_account_closed_positions(
+    app: &AppContext,
+    request: &TradingExecutorGetAccountClosedPositionsGrpcRequest,
+) -> (
+    Vec<TradingExecutorClosedPositionHistoryGrpcModel>,
+    TradingExecutorClosedPositionsSummaryGrpcModel,
+) {
+    let filter = ClosedPositionsFilter {
+        from: request.from_unix_timestamp_milliseconds,
+        to: request.to_unix_timestamp_milliseconds,
+        asset_pair: request.asset_pair.clone(),
+    };
+
+    let mut positions = app
+        .closed_positions_history
+        .get(&request.trader_id, &request.account_id, &filter)
+        .await;
+
+    positions.sort_by_key(|x| x.close_date_unix_timestamp_milliseconds);
+
+    let summary = get_closed_positions_summary(&positions);
+
+    (positions, summary)
+}
+
+pub fn get_closed_positions_summary(
+    positions: &[TradingExecutorClosedPositionHistoryGrpcModel],
+) -> TradingExecutorClosedPositionsSummaryGrpcModel {
+    let mut summary = TradingExecutorClosedPositionsSummaryGrpcModel::default();
+
+    for record in positions {
+        let profit = record
+            .position
+            .as_ref()
+            .map(|x| x.profit)
+            .unwrap_or_default();
+
+        summary.positions_count += 1;
+        summary.realized_pnl += profit;
+        summary.commission += record.commission;
+
+        if profit > 0.0 {
+            summary.profitable_positions_count += 1;
+        } else if profit < 0.0 {
+            summary.losing_positions_count += 1;
+        }
+    }
+
+    summary.net_pnl = summary.realized_pnl - summary.commission;
+
+    summary
+}
+
+#[cfg(test)]
+mod test {
+    use crate::trading_executor_grpc::{
+        TradingExecutorClosedPositionGrpcModel, TradingExecutorClosedPositionHistoryGrpcModel,
+    };
+
+    use super::get_closed_positions_summary;
+
+    fn record(profit: f64, commission: f64) -> TradingExecutorClosedPositionHistoryGrpcModel {
+        TradingExecutorClosedPositionHistoryGrpcModel {
+            commission,
+            position: Some(TradingExecutorClosedPositionGrpcModel {
+                profit,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn summary_of_closed_positions() {
+        let summary =
+            get_closed_positions_summary(&[record(10.0, 1.0), record(-4.0, 0.5), record(0.0, 0.0)]);
+
+        assert_eq!(3, summary.positions_count);
+        assert_eq!(1, summary.profitable_positions_count);
+        assert_eq!(1, summary.losing_positions_count);
+        assert!((summary.realized_pnl - 6.0).abs() < 1e-9);
+        assert!((summary.net_pnl - 4.5).abs() < 1e-9);
+    }
+}
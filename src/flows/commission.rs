@@ -0,0 +1,77 @@
+use my_nosql_contracts::TradingInstrumentNoSqlEntity;
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{
+    accounts_manager_grpc::UpdateBalanceReason, get_contract_size, resolve_cross_rate,
+    update_account_balance, AppContext, TradingExecutorError,
+};
+
+/// Calculates the commission for a trade of `notional` (in collateral currency) and returns
+/// it in collateral currency. Returns 0 when the profile instrument has no commission rules.
+pub async fn calculate_commission(
+    app: &AppContext,
+    trading_profile_id: &str,
+    instrument: &TradingInstrumentNoSqlEntity,
+    collateral: &str,
+    notional: f64,
+) -> Result<f64, TradingExecutorError> {
+    let Some(profile_instrument_settings) = app
+        .trading_profile_instrument_settings_reader
+        .get_entity(trading_profile_id, instrument.get_id())
+        .await
+    else {
+        return Ok(0.0);
+    };
+
+    let commission_settings = profile_instrument_settings.get_commission_settings();
+
+    if !commission_settings.is_configured() {
+        return Ok(0.0);
+    }
+
+    let lots = match commission_settings.per_lot {
+        Some(_) => {
+            let contract_size = get_contract_size(app, instrument).await?;
+            let base_collateral = resolve_cross_rate(app, &instrument.base, collateral).await?;
+            notional / base_collateral.rate / contract_size
+        }
+        None => 0.0,
+    };
+
+    let commission_currency = commission_settings
+        .currency
+        .as_deref()
+        .unwrap_or(collateral);
+
+    let collateral_commission = resolve_cross_rate(app, collateral, commission_currency).await?;
+    let commission = commission_settings.calculate(notional * collateral_commission.rate, lots);
+
+    Ok(commission / collateral_commission.rate)
+}
+
+pub async fn charge_commission(
+    app: &AppContext,
+    trader_id: &str,
+    account_id: &str,
+    process_id: String,
+    delta: f64,
+    comment: &str,
+    allow_negative_balance: bool,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<(), TradingExecutorError> {
+    // `UpdateBalanceReason` comes from the Accounts Manager proto synced from proto-files at
+    // build, which has no commission reason. Commission charges are told apart from trading
+    // results by the comment and the `-commission` process id suffix.
+    update_account_balance(
+        app,
+        trader_id,
+        account_id,
+        process_id,
+        delta,
+        UpdateBalanceReason::TradingResult,
+        comment,
+        allow_negative_balance,
+        telemetry_context,
+    )
+    .await
+}
@@ -0,0 +1,231 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use my_nosql_contracts::TradingInstrumentNoSqlEntity;
+
+use crate::{validate_instrument_timeout, AppContext, TradingExecutorError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionHop {
+    pub instrument_id: String,
+    /// Conversion goes from the pair quote to the pair base.
+    pub is_inverted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrossRate {
+    pub rate: f64,
+    pub path: Vec<ConversionHop>,
+}
+
+/// Resolves the rate for converting one unit of `from` into `to` using the shortest chain of
+/// available bid-asks. Every hop must have a fresh price. Multi-hop paths are searched over
+/// tradable instruments only and cached per (from, to).
+pub async fn resolve_cross_rate(
+    app: &AppContext,
+    from: &str,
+    to: &str,
+) -> Result<CrossRate, TradingExecutorError> {
+    if from == to {
+        return Ok(CrossRate {
+            rate: 1.0,
+            path: vec![],
+        });
+    }
+
+    let path = match app.bid_ask_cache.find_pair(from, to).await {
+        Some(bid_ask) => vec![ConversionHop {
+            instrument_id: bid_ask.row_key.clone(),
+            is_inverted: bid_ask.base != from,
+        }],
+        None => match app.bid_ask_cache.get_conversion_path(from, to).await {
+            Some(path) => path,
+            None => {
+                let path = find_tradable_conversion_path(app, from, to).await?;
+                app.bid_ask_cache
+                    .set_conversion_path(from, to, path.clone())
+                    .await;
+                path
+            }
+        },
+    };
+
+    let mut rate = 1.0;
+
+    for hop in &path {
+        let Some(bid_ask) = app.bid_ask_cache.get_by_id(&hop.instrument_id).await else {
+            return Err(TradingExecutorError::NoLiquidity);
+        };
+
+        let Some(instrument) = app
+            .trading_instruments_reader
+            .get_entity(
+                TradingInstrumentNoSqlEntity::generate_partition_key(),
+                &hop.instrument_id,
+            )
+            .await
+        else {
+            // The instrument was removed after the path was cached
+            app.bid_ask_cache.remove_conversion_path(from, to).await;
+            return Err(TradingExecutorError::InstrumentNotFound);
+        };
+
+        validate_instrument_timeout(app, &instrument, bid_ask.unix_timestamp_with_milis).await?;
+
+        rate *= get_hop_rate(hop, bid_ask.bid, bid_ask.ask);
+    }
+
+    Ok(CrossRate { rate, path })
+}
+
+async fn find_tradable_conversion_path(
+    app: &AppContext,
+    from: &str,
+    to: &str,
+) -> Result<Vec<ConversionHop>, TradingExecutorError> {
+    let instrument_ids: HashSet<String> = app
+        .trading_instruments_reader
+        .get_by_partition_key_as_vec(TradingInstrumentNoSqlEntity::generate_partition_key())
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|x| x.get_id().to_string())
+        .collect();
+
+    let bid_asks = app.bid_ask_cache.get_all().await;
+    let pairs = bid_asks
+        .iter()
+        .filter(|x| instrument_ids.contains(&x.row_key))
+        .map(|x| (x.row_key.as_str(), x.base.as_str(), x.quote.as_str()));
+
+    find_conversion_path(from, to, pairs).ok_or(TradingExecutorError::NoLiquidity)
+}
+
+pub fn get_hop_rate(hop: &ConversionHop, bid: f64, ask: f64) -> f64 {
+    match hop.is_inverted {
+        false => bid,
+        true => 1.0 / ask,
+    }
+}
+
+/// Breadth-first search over currencies, where every `(instrument_id, base, quote)` pair
+/// links its two currencies in both directions.
+pub fn find_conversion_path<'s>(
+    from: &str,
+    to: &str,
+    pairs: impl Iterator<Item = (&'s str, &'s str, &'s str)>,
+) -> Option<Vec<ConversionHop>> {
+    let mut links: HashMap<&str, Vec<(&str, ConversionHop)>> = HashMap::new();
+
+    for (instrument_id, base, quote) in pairs {
+        links.entry(base).or_default().push((
+            quote,
+            ConversionHop {
+                instrument_id: instrument_id.to_string(),
+                is_inverted: false,
+            },
+        ));
+        links.entry(quote).or_default().push((
+            base,
+            ConversionHop {
+                instrument_id: instrument_id.to_string(),
+                is_inverted: true,
+            },
+        ));
+    }
+
+    let mut visited = HashSet::new();
+    visited.insert(from);
+
+    let mut queue = VecDeque::new();
+    queue.push_back((from, vec![]));
+
+    while let Some((currency, path)) = queue.pop_front() {
+        let Some(currency_links) = links.get(currency) else {
+            continue;
+        };
+
+        for (next_currency, hop) in currency_links {
+            if !visited.insert(*next_currency) {
+                continue;
+            }
+
+            let mut next_path: Vec<ConversionHop> = path.clone();
+            next_path.push(hop.clone());
+
+            if *next_currency == to {
+                return Some(next_path);
+            }
+
+            queue.push_back((*next_currency, next_path));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_conversion_path, get_hop_rate, ConversionHop};
+
+    const PAIRS: [(&str, &str, &str); 4] = [
+        ("EURUSD", "EUR", "USD"),
+        ("USDJPY", "USD", "JPY"),
+        ("GBPUSD", "GBP", "USD"),
+        ("XAUUSD", "XAU", "USD"),
+    ];
+
+    #[test]
+    fn find_direct_path() {
+        let path = find_conversion_path("EUR", "USD", PAIRS.into_iter()).unwrap();
+
+        assert_eq!(
+            vec![ConversionHop {
+                instrument_id: "EURUSD".to_string(),
+                is_inverted: false,
+            }],
+            path
+        );
+    }
+
+    #[test]
+    fn find_path_via_usd() {
+        let path = find_conversion_path("JPY", "EUR", PAIRS.into_iter()).unwrap();
+
+        assert_eq!(
+            vec![
+                ConversionHop {
+                    instrument_id: "USDJPY".to_string(),
+                    is_inverted: true,
+                },
+                ConversionHop {
+                    instrument_id: "EURUSD".to_string(),
+                    is_inverted: true,
+                },
+            ],
+            path
+        );
+    }
+
+    #[test]
+    fn no_path_for_unknown_currency() {
+        let path = find_conversion_path("CHF", "EUR", PAIRS.into_iter());
+
+        assert_eq!(None, path);
+    }
+
+    #[test]
+    fn cross_rate_through_two_hops() {
+        let path = find_conversion_path("GBP", "JPY", PAIRS.into_iter()).unwrap();
+        let prices = [("GBPUSD", 1.25, 1.2502), ("USDJPY", 150.0, 150.02)];
+
+        let rate = path.iter().fold(1.0, |rate, hop| {
+            let (_, bid, ask) = prices
+                .iter()
+                .find(|(id, _, _)| *id == hop.instrument_id)
+                .unwrap();
+            rate * get_hop_rate(hop, *bid, *ask)
+        });
+
+        assert!((rate - 187.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,80 @@
+use std::{fmt::Debug, future::Future, time::Instant};
+
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{DownstreamError, DownstreamService, TradingExecutorError};
+
+/// Request identity used to record failed downstream calls in the trade log.
+pub struct DownstreamContext<'s> {
+    pub trader_id: &'s str,
+    pub account_id: &'s str,
+    pub process_id: &'s str,
+    pub telemetry_context: &'s MyTelemetryContext,
+}
+
+impl<'s> DownstreamContext<'s> {
+    pub fn new(
+        trader_id: &'s str,
+        account_id: &'s str,
+        process_id: &'s str,
+        telemetry_context: &'s MyTelemetryContext,
+    ) -> Self {
+        Self {
+            trader_id,
+            account_id,
+            process_id,
+            telemetry_context,
+        }
+    }
+
+    pub async fn call<T, E: Debug>(
+        &self,
+        service: DownstreamService,
+        method: &'static str,
+        call: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, TradingExecutorError> {
+        let started = Instant::now();
+
+        match call.await {
+            Ok(result) => Ok(result),
+            Err(err) => {
+                let error = DownstreamError {
+                    service,
+                    method,
+                    status: format!("{:?}", err),
+                    elapsed_ms: started.elapsed().as_millis() as u64,
+                };
+
+                trade_log::trade_log!(
+                    self.trader_id,
+                    self.account_id,
+                    self.process_id,
+                    "n/a",
+                    "Downstream call failed",
+                    self.telemetry_context.clone(),
+                    "error" = &error,
+                    "trace_id" = &self.telemetry_context.as_string()
+                );
+
+                Err(TradingExecutorError::Downstream(error))
+            }
+        }
+    }
+
+    /// Unknown status codes are mapped to `TechError`; the raw code is kept in the trade log.
+    pub fn unknown_code(&self, service: DownstreamService, value: i32) -> TradingExecutorError {
+        trade_log::trade_log!(
+            self.trader_id,
+            self.account_id,
+            self.process_id,
+            "n/a",
+            "Unknown downstream status code",
+            self.telemetry_context.clone(),
+            "service" = &service,
+            "status_code" = &value.to_string(),
+            "trace_id" = &self.telemetry_context.as_string()
+        );
+
+        TradingExecutorError::TechError
+    }
+}
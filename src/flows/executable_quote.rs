@@ -0,0 +1,169 @@
+use std::{collections::HashSet, sync::Arc};
+
+use my_nosql_contracts::{BidAskSnapshotNoSqlEntity, TradingGroupNoSqlEntity};
+use service_sdk::my_telemetry::MyTelemetryContext;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::{
+    accounts_manager_grpc::AccountManagerGetClientAccountGrpcRequest,
+    trading_executor_grpc::{
+        TradingExecutorBidAsk, TradingExecutorGetQuoteGrpcRequest,
+        TradingExecutorSubscribeQuotesGrpcRequest,
+    },
+    AppContext, DownstreamContext, DownstreamService, ExecutableBidAsk, MarkupSettings,
+    TradingExecutorError,
+};
+
+const QUOTES_STREAM_BUFFER: usize = 1024;
+
+pub async fn get_quote(
+    app: &Arc<AppContext>,
+    request: TradingExecutorGetQuoteGrpcRequest,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<TradingExecutorBidAsk, TradingExecutorError> {
+    let trading_profile_id = get_account_trading_profile_id(
+        app,
+        &request.trader_id,
+        &request.account_id,
+        telemetry_context,
+    )
+    .await?;
+
+    let bid_ask = get_executable_bid_ask(app, &trading_profile_id, &request.asset_pair).await?;
+
+    Ok((&bid_ask).into())
+}
+
+pub async fn subscribe_quotes(
+    app: &Arc<AppContext>,
+    request: TradingExecutorSubscribeQuotesGrpcRequest,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<mpsc::Receiver<Result<TradingExecutorBidAsk, tonic::Status>>, TradingExecutorError> {
+    let trading_profile_id = get_account_trading_profile_id(
+        app,
+        &request.trader_id,
+        &request.account_id,
+        telemetry_context,
+    )
+    .await?;
+
+    let asset_pairs: HashSet<String> = request.asset_pairs.into_iter().collect();
+    let (sender, receiver) = mpsc::channel(QUOTES_STREAM_BUFFER);
+    let mut updates = app.bid_ask_cache.subscribe();
+    let app = app.clone();
+
+    tokio::spawn(async move {
+        let snapshot = app.bid_ask_cache.get_all().await;
+
+        for bid_ask in snapshot {
+            if !asset_pairs.is_empty() && !asset_pairs.contains(&bid_ask.row_key) {
+                continue;
+            }
+
+            let quote = apply_profile_markup(&app, &trading_profile_id, &bid_ask).await;
+            if sender.send(Ok((&quote).into())).await.is_err() {
+                return;
+            }
+        }
+
+        loop {
+            let bid_ask = match updates.recv().await {
+                Ok(bid_ask) => bid_ask,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return,
+            };
+
+            if !asset_pairs.is_empty() && !asset_pairs.contains(&bid_ask.row_key) {
+                continue;
+            }
+
+            let quote = apply_profile_markup(&app, &trading_profile_id, &bid_ask).await;
+            if sender.send(Ok((&quote).into())).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+pub async fn get_account_trading_profile_id(
+    app: &AppContext,
+    trader_id: &str,
+    account_id: &str,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<String, TradingExecutorError> {
+    let downstream = DownstreamContext::new(trader_id, account_id, "n/a", telemetry_context);
+
+    let Some(account) = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "get_client_account",
+            app.accounts_manager_grpc_client.get_client_account(
+                AccountManagerGetClientAccountGrpcRequest {
+                    trader_id: trader_id.to_string(),
+                    account_id: account_id.to_string(),
+                },
+                telemetry_context,
+            ),
+        )
+        .await?
+        .account
+    else {
+        return Err(TradingExecutorError::AccountNotFound);
+    };
+
+    let Some(trading_group) = app
+        .trading_groups_reader
+        .get_entity(
+            TradingGroupNoSqlEntity::generate_partition_key(),
+            &account.trading_group,
+        )
+        .await
+    else {
+        return Err(TradingExecutorError::TradingGroupNotFound);
+    };
+
+    Ok(trading_group.trading_profile_id.clone())
+}
+
+pub async fn get_executable_bid_ask(
+    app: &AppContext,
+    trading_profile_id: &str,
+    asset_pair: &str,
+) -> Result<ExecutableBidAsk, TradingExecutorError> {
+    let Some(bid_ask) = app.bid_ask_cache.get_by_id(asset_pair).await else {
+        return Err(TradingExecutorError::NoLiquidity);
+    };
+
+    Ok(apply_profile_markup(app, trading_profile_id, &bid_ask).await)
+}
+
+pub async fn get_markup_settings(
+    app: &AppContext,
+    trading_profile_id: &str,
+    asset_pair: &str,
+) -> Option<MarkupSettings> {
+    app.trading_profile_instrument_settings_reader
+        .get_entity(trading_profile_id, asset_pair)
+        .await
+        .map(|x| x.get_markup_settings())
+}
+
+pub async fn apply_profile_markup(
+    app: &AppContext,
+    trading_profile_id: &str,
+    bid_ask: &BidAskSnapshotNoSqlEntity,
+) -> ExecutableBidAsk {
+    let (bid, ask) = match get_markup_settings(app, trading_profile_id, &bid_ask.row_key).await {
+        Some(markup_settings) => markup_settings.apply(bid_ask.bid, bid_ask.ask),
+        None => (bid_ask.bid, bid_ask.ask),
+    };
+
+    ExecutableBidAsk {
+        asset_pair: bid_ask.row_key.clone(),
+        bid,
+        ask,
+        date_time_unix_timestamp_milis: bid_ask.unix_timestamp_with_milis,
+    }
+}
@@ -0,0 +1,251 @@
+use my_nosql_contracts::TradingProfileNoSqlEntity;
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{
+    accounts_manager_grpc::AccountGrpcModel,
+    get_executable_bid_ask,
+    position_manager_grpc::{
+        PositionManagerActivePositionGrpcModel, PositionManagerGetActivePositionsGrpcRequest,
+        PositionManagerPositionSide,
+    },
+    resolve_cross_rate, AppContext, DownstreamContext, DownstreamService, TradingExecutorError,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct AccountMargin {
+    pub balance: f64,
+    pub equity: f64,
+    pub floating_pnl: f64,
+    pub used_margin: f64,
+    pub free_margin: f64,
+    pub margin_level: Option<f64>,
+}
+
+pub struct RevaluedPosition {
+    pub position: PositionManagerActivePositionGrpcModel,
+    pub pnl: f64,
+}
+
+pub async fn calculate_account_margin(
+    app: &AppContext,
+    account: &AccountGrpcModel,
+    trading_profile_id: &str,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<(AccountMargin, Vec<RevaluedPosition>), TradingExecutorError> {
+    let downstream =
+        DownstreamContext::new(&account.trader_id, &account.id, "n/a", telemetry_context);
+
+    let positions = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "get_account_active_positions",
+            app.position_manager_grpc_client
+                .get_account_active_positions(
+                    PositionManagerGetActivePositionsGrpcRequest {
+                        trader_id: account.trader_id.clone(),
+                        account_id: account.id.clone(),
+                    },
+                    telemetry_context,
+                ),
+        )
+        .await?
+        .unwrap_or_default();
+
+    let mut revalued_positions = Vec::with_capacity(positions.len());
+
+    for position in positions {
+        let pnl = revalue_position(app, &position, trading_profile_id).await;
+        revalued_positions.push(RevaluedPosition { position, pnl });
+    }
+
+    let margin = get_account_margin(
+        account.balance,
+        revalued_positions
+            .iter()
+            .map(|x| (x.position.invest_amount, x.pnl)),
+    );
+
+    Ok((margin, revalued_positions))
+}
+
+/// Floating PnL in collateral currency, including charged swaps. Falls back to the profit
+/// reported by the Position Manager when there is no fresh price to revalue with.
+pub async fn revalue_position(
+    app: &AppContext,
+    position: &PositionManagerActivePositionGrpcModel,
+    trading_profile_id: &str,
+) -> f64 {
+    let swaps: f64 = position.swaps.iter().map(|x| x.swap_amount).sum();
+
+    let Ok(bid_ask) = get_executable_bid_ask(app, trading_profile_id, &position.asset_pair).await
+    else {
+        return position.profit;
+    };
+
+    let Ok(quote_collateral) = resolve_cross_rate(app, &position.quote, &position.collateral).await
+    else {
+        return position.profit;
+    };
+
+    let is_buy = position.side() == PositionManagerPositionSide::Buy;
+
+    calculate_position_pnl(
+        is_buy,
+        position.open_price,
+        bid_ask.get_close_price(is_buy),
+        position.invest_amount * position.leverage,
+        position.base_collateral_open_price,
+        quote_collateral.rate,
+    ) + swaps
+}
+
+/// `notional` is in collateral currency, converted into base units at the open rate.
+pub fn calculate_position_pnl(
+    is_buy: bool,
+    open_price: f64,
+    close_price: f64,
+    notional: f64,
+    base_collateral_open_price: f64,
+    quote_collateral_rate: f64,
+) -> f64 {
+    if base_collateral_open_price == 0.0 {
+        return 0.0;
+    }
+
+    let units = notional / base_collateral_open_price;
+    let price_change = match is_buy {
+        true => close_price - open_price,
+        false => open_price - close_price,
+    };
+
+    units * price_change * quote_collateral_rate
+}
+
+/// Invest amounts are debited from the balance on open, so they come back into equity
+/// together with the floating result.
+pub fn get_account_margin(
+    balance: f64,
+    positions: impl Iterator<Item = (f64, f64)>,
+) -> AccountMargin {
+    let mut used_margin = 0.0;
+    let mut floating_pnl = 0.0;
+
+    for (invest_amount, pnl) in positions {
+        used_margin += invest_amount;
+        floating_pnl += pnl;
+    }
+
+    let equity = balance + used_margin + floating_pnl;
+
+    AccountMargin {
+        balance,
+        equity,
+        floating_pnl,
+        used_margin,
+        free_margin: equity - used_margin,
+        margin_level: get_margin_level(equity, used_margin),
+    }
+}
+
+pub fn get_margin_level(equity: f64, used_margin: f64) -> Option<f64> {
+    if used_margin <= 0.0 {
+        return None;
+    }
+
+    Some(equity / used_margin * 100.0)
+}
+
+/// Margin level an open must keep. The stop out level is not used as a fallback, an open
+/// right above it would be stopped out by the next tick.
+pub async fn get_open_min_margin_level(
+    app: &AppContext,
+    trading_profile_id: &str,
+    trading_profile: &TradingProfileNoSqlEntity,
+    instrument_id: &str,
+) -> Result<f64, TradingExecutorError> {
+    let open_min_margin_level = app
+        .trading_profile_instrument_settings_reader
+        .get_entity(trading_profile_id, instrument_id)
+        .await
+        .and_then(|x| x.open_min_margin_level);
+
+    open_min_margin_level
+        .or(trading_profile.margin_call_percent)
+        .ok_or(TradingExecutorError::MarginCallSettingsNotFound)
+}
+
+pub fn validate_open_margin(
+    margin: &AccountMargin,
+    invest_amount: f64,
+    commission: f64,
+    min_margin_level: f64,
+) -> Result<(), TradingExecutorError> {
+    if margin.free_margin < invest_amount + commission {
+        return Err(TradingExecutorError::NotEnoughFreeMargin {
+            free_margin: margin.free_margin,
+            required: invest_amount + commission,
+        });
+    }
+
+    let margin_level = get_margin_level(
+        margin.equity - commission,
+        margin.used_margin + invest_amount,
+    );
+
+    if let Some(margin_level) = margin_level {
+        if margin_level < min_margin_level {
+            return Err(TradingExecutorError::MarginLevelTooLow {
+                margin_level,
+                min_margin_level,
+            });
+        }
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod test {
+    use super::{calculate_position_pnl, get_account_margin, validate_open_margin};
+
+    #[test]
+    fn buy_position_pnl() {
+        let pnl = calculate_position_pnl(true, 1.1000, 1.1100, 110_000.0, 1.1000, 1.0);
+
+        assert!((pnl - 1_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sell_position_pnl_with_quote_conversion() {
+        let pnl = calculate_position_pnl(false, 150.0, 149.0, 10_000.0, 1.0, 1.0 / 149.0);
+
+        assert!((pnl - 10_000.0 / 149.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn account_margin() {
+        let margin = get_account_margin(900.0, vec![(100.0, -20.0)].into_iter());
+
+        assert!((margin.equity - 980.0).abs() < 1e-9);
+        assert!((margin.free_margin - 880.0).abs() < 1e-9);
+        assert!((margin.margin_level.unwrap() - 980.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reject_open_below_margin_level() {
+        let margin = get_account_margin(100.0, vec![(100.0, -50.0)].into_iter());
+
+        let result = validate_open_margin(&margin, 100.0, 0.0, 100.0);
+
+        assert_eq!(true, result.is_err());
+    }
+
+    #[test]
+    fn allow_open_above_margin_level() {
+        let margin = get_account_margin(1_000.0, vec![(100.0, 10.0)].into_iter());
+
+        let result = validate_open_margin(&margin, 100.0, 1.0, 100.0);
+
+        assert_eq!(false, result.is_err());
+    }
+}
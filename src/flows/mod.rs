@@ -4,10 +4,46 @@ mod update_sl_tp;
 mod open_limit;
 mod validate_prices_timeout;
 mod validate_day_off;
+mod cancel_all_pending;
+mod validate_market_state;
+mod trading_schedule;
+mod validate_holidays;
+mod validate_pre_close;
+mod cross_rate;
+mod executable_quote;
+mod commission;
+mod margin;
+mod account_summary;
+mod position_updates;
+mod closed_positions_history;
+mod downstream;
+mod validate_leverage;
+mod check_open_position;
+mod order_sizing;
+mod open_position_validation;
+mod account_balance;
 
 pub use open_position_flow::*;
 pub use close_position::*;
 pub use update_sl_tp::*;
 pub use open_limit::*;
 pub use validate_prices_timeout::*;
-pub use validate_day_off::*;
\ No newline at end of file
+pub use validate_day_off::*;
+pub use cancel_all_pending::*;
+pub use validate_market_state::*;
+pub use trading_schedule::*;
+pub use validate_holidays::*;
+pub use validate_pre_close::*;
+pub use cross_rate::*;
+pub use executable_quote::*;
+pub use commission::*;
+pub use margin::*;
+pub use account_summary::*;
+pub use position_updates::*;
+pub use closed_positions_history::*;
+pub use downstream::*;
+pub use validate_leverage::*;
+pub use check_open_position::*;
+pub use order_sizing::*;
+pub use open_position_validation::*;
+pub use account_balance::*;
\ No newline at end of file
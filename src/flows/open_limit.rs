@@ -7,14 +7,18 @@ use service_sdk::my_telemetry::MyTelemetryContext;
 
 use crate::{
     accounts_manager_grpc::AccountManagerGetClientAccountGrpcRequest,
+    get_allowed_leverages,
     position_manager_grpc::{
-        PositionManagerCancelPendingGrpcRequest, PositionManagerOpenPendingGrpcRequest,
+        PositionManagerCancelPendingGrpcRequest, PositionManagerGetPendingPositionGrpcRequest,
+        PositionManagerOpenPendingGrpcRequest,
     },
+    resolve_invest_amount,
     trading_executor_grpc::{
         TradingExecutorCancelPendingGrpcRequest, TradingExecutorOpenPendingGrpcRequest,
-        TradingExecutorPendingPositionGrpcModel,
+        TradingExecutorPendingPositionGrpcModel, TradingExecutorPositionSide,
     },
-    AppContext, TradingExecutorError,
+    validate_leverage, validate_leverage_tier, validate_market_state, AppContext,
+    DownstreamContext, DownstreamService, OrderSize, TradingExecutorError, TradingOperationType,
 };
 
 pub async fn open_limit(
@@ -22,6 +26,13 @@ pub async fn open_limit(
     request: TradingExecutorOpenPendingGrpcRequest,
     telemetry_context: &MyTelemetryContext,
 ) -> Result<TradingExecutorPendingPositionGrpcModel, TradingExecutorError> {
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        telemetry_context,
+    );
+
     let position_id = uuid::Uuid::new_v4().to_string();
 
     let target_instrument = app
@@ -35,22 +46,33 @@ pub async fn open_limit(
     let Some(target_instrument) = target_instrument else {
         return Err(TradingExecutorError::InstrumentNotFound);
     };
-    let Some(target_account) = app
-        .accounts_manager_grpc_client
-        .get_client_account(
-            AccountManagerGetClientAccountGrpcRequest {
-                trader_id: request.trader_id.clone(),
-                account_id: request.account_id.clone(),
-            },
-            telemetry_context,
+    let Some(target_account) = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "get_client_account",
+            app.accounts_manager_grpc_client.get_client_account(
+                AccountManagerGetClientAccountGrpcRequest {
+                    trader_id: request.trader_id.clone(),
+                    account_id: request.account_id.clone(),
+                },
+                telemetry_context,
+            ),
         )
-        .await
-        .unwrap()
+        .await?
         .account
     else {
         return Err(TradingExecutorError::AccountNotFound);
     };
 
+    validate_market_state(
+        app,
+        &downstream,
+        TradingOperationType::PlacePending,
+        &target_instrument,
+        &target_account.currency,
+    )
+    .await?;
+
     let Some(target_trading_group) = app
         .trading_groups_reader
         .get_entity(
@@ -81,18 +103,52 @@ pub async fn open_limit(
         return Err(TradingExecutorError::TradingProfileInstrumentNotFound);
     };
 
-    if !target_trading_profile_instrument
-        .leverages
-        .contains(&request.leverage)
-    {
-        return Err(TradingExecutorError::MultiplierIsNotFound);
-    }
+    let allowed_leverages = get_allowed_leverages(
+        app,
+        &request.account_id,
+        &target_trading_group.trading_profile_id,
+        &request.asset_pair,
+        &target_trading_profile_instrument.leverages,
+    )
+    .await;
+
+    validate_leverage(&allowed_leverages, request.get_leverage())?;
+
+    let order_size = OrderSize::new(
+        request.sizing_mode(),
+        request.invest_amount,
+        request.sizing_value,
+        request.side() == TradingExecutorPositionSide::Buy,
+        request.sl_in_asset_price,
+    )?;
+
+    let invest_amount = resolve_invest_amount(
+        app,
+        order_size,
+        request.get_leverage(),
+        Some(request.desire_price),
+        &target_instrument,
+        &target_account,
+        &target_trading_group.trading_profile_id,
+        telemetry_context,
+    )
+    .await?;
+
+    validate_leverage_tier(
+        app,
+        &downstream,
+        &target_trading_group.trading_profile_id,
+        &request.asset_pair,
+        request.get_leverage(),
+        invest_amount,
+    )
+    .await?;
 
     let open_position_request = PositionManagerOpenPendingGrpcRequest {
         asset_pair: request.asset_pair.clone(),
         side: request.side,
-        invest_amount: request.invest_amount,
-        leverage: request.leverage as f64,
+        invest_amount,
+        leverage: request.get_leverage(),
         stop_out_percent: target_trading_profile.stop_out_percent,
         process_id: request.process_id.clone(),
         tp_in_profit: request.tp_in_profit,
@@ -114,11 +170,14 @@ pub async fn open_limit(
         margin_call_percent: target_trading_profile.margin_call_percent,
     };
 
-    let position = app
-        .position_manager_grpc_client
-        .open_pending(open_position_request, telemetry_context)
-        .await
-        .unwrap();
+    let position = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "open_pending",
+            app.position_manager_grpc_client
+                .open_pending(open_position_request, telemetry_context),
+        )
+        .await?;
 
     let position = position
         .position
@@ -134,17 +193,90 @@ pub async fn cancel_pending(
     request: TradingExecutorCancelPendingGrpcRequest,
     telemetry_context: &MyTelemetryContext,
 ) -> Result<TradingExecutorPendingPositionGrpcModel, TradingExecutorError> {
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        telemetry_context,
+    );
+
+    let checks = app
+        .market_state_policy
+        .get_checks(TradingOperationType::CancelPending);
+
+    if checks.day_off || checks.liquidity {
+        let Some(target_position) = downstream
+            .call(
+                DownstreamService::PositionManager,
+                "get_pending_position",
+                app.position_manager_grpc_client.get_pending_position(
+                    PositionManagerGetPendingPositionGrpcRequest {
+                        trader_id: request.trader_id.clone(),
+                        account_id: request.account_id.clone(),
+                        id: request.position_id.clone(),
+                    },
+                    telemetry_context,
+                ),
+            )
+            .await?
+            .position
+        else {
+            return Err(TradingExecutorError::PositionNotFound);
+        };
+
+        let Some(target_instrument) = app
+            .trading_instruments_reader
+            .get_entity(
+                TradingInstrumentNoSqlEntity::generate_partition_key(),
+                &target_position.asset_pair,
+            )
+            .await
+        else {
+            return Err(TradingExecutorError::InstrumentNotFound);
+        };
+
+        let Some(target_account) = downstream
+            .call(
+                DownstreamService::AccountsManager,
+                "get_client_account",
+                app.accounts_manager_grpc_client.get_client_account(
+                    AccountManagerGetClientAccountGrpcRequest {
+                        trader_id: request.trader_id.clone(),
+                        account_id: request.account_id.clone(),
+                    },
+                    telemetry_context,
+                ),
+            )
+            .await?
+            .account
+        else {
+            return Err(TradingExecutorError::AccountNotFound);
+        };
+
+        validate_market_state(
+            app,
+            &downstream,
+            TradingOperationType::CancelPending,
+            &target_instrument,
+            &target_account.currency,
+        )
+        .await?;
+    }
+
     let cancel_request = PositionManagerCancelPendingGrpcRequest {
-        account_id: request.account_id,
-        trader_id: request.trader_id,
+        account_id: request.account_id.clone(),
+        trader_id: request.trader_id.clone(),
         id: request.position_id,
     };
 
-    let position = app
-        .position_manager_grpc_client
-        .cancel_pending(cancel_request, telemetry_context)
-        .await
-        .unwrap();
+    let position = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "cancel_pending",
+            app.position_manager_grpc_client
+                .cancel_pending(cancel_request, telemetry_context),
+        )
+        .await?;
 
     let position = position
         .position
@@ -6,19 +6,22 @@ use tokio::time::sleep;
 use uuid::Uuid;
 
 use crate::{
-    a_book_bridge_grpc::{self, ABookBridgeOpenPositionGrpcRequest, ABookBridgePositionSide},
+    a_book_bridge_grpc::{
+        self, ABookBridgeOpenPositionGrpcRequest, ABookBridgePositionSide,
+        OpenPositionGrpcResponseStatusCode,
+    },
     accounts_manager_grpc::{
-        AccountManagerGetClientAccountGrpcRequest, AccountManagerUpdateAccountBalanceGrpcRequest,
-        AccountsManagerOperationResult, UpdateBalanceReason,
+        AccountManagerUpdateAccountBalanceGrpcRequest, AccountsManagerOperationResult,
+        UpdateBalanceReason,
     },
+    charge_commission,
     position_manager_grpc::PositionManagerOpenPositionGrpcRequest,
     trading_executor_grpc::{
         TradingExecutorActivePositionGrpcModel, TradingExecutorOpenPositionGrpcRequest,
+        TradingExecutorPositionSide,
     },
-    validate_instrument_day_off, validate_timeout, AppContext, TradingExecutorError,
-};
-use my_nosql_contracts::{
-    TradingGroupNoSqlEntity, TradingInstrumentNoSqlEntity, TradingProfileNoSqlEntity,
+    validate_open_position_request, validate_open_position_requirements, AppContext,
+    DownstreamContext, DownstreamService, FailFastChecks, TradingExecutorError,
 };
 use service_sdk::my_telemetry::{self, MyTelemetryContext};
 
@@ -26,143 +29,53 @@ pub async fn open_position(
     app: &Arc<AppContext>,
     request: TradingExecutorOpenPositionGrpcRequest,
     telemetry_context: &my_telemetry::MyTelemetryContext,
-) -> Result<TradingExecutorActivePositionGrpcModel, TradingExecutorError> {
+) -> Result<(TradingExecutorActivePositionGrpcModel, f64), TradingExecutorError> {
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        telemetry_context,
+    );
     let position_id = uuid::Uuid::new_v4().to_string();
 
-    let target_instrument = app
-        .trading_instruments_reader
-        .get_entity(
-            TradingInstrumentNoSqlEntity::generate_partition_key(),
-            &request.asset_pair,
-        )
-        .await;
-
-    let Some(target_instrument) = target_instrument else {
-        return Err(TradingExecutorError::InstrumentNotFound);
-    };
+    let validated =
+        validate_open_position_request(app, &request, &downstream, &mut FailFastChecks).await?;
 
-    validate_instrument_day_off(&target_instrument)?;
+    let delay = delay_open(
+        validated.open_position_min_delay_ms,
+        validated.open_position_max_delay_ms,
+    )
+    .await;
 
-    let Some(target_account) = app
-        .accounts_manager_grpc_client
-        .get_client_account(
-            AccountManagerGetClientAccountGrpcRequest {
-                trader_id: request.trader_id.clone(),
-                account_id: request.account_id.clone(),
-            },
-            telemetry_context,
-        )
-        .await
-        .unwrap()
-        .account
-    else {
-        return Err(TradingExecutorError::AccountNotFound);
-    };
+    println!("Open delay: {} ms", delay);
+    sleep(Duration::from_millis(delay as u64)).await;
 
-    validate_timeout(
+    let requirements = validate_open_position_requirements(
         app,
-        &target_instrument,
-        &target_instrument.base,
-        &target_instrument.quote,
-        &target_account.currency,
+        &request,
+        &validated,
+        &mut FailFastChecks,
+        telemetry_context,
     )
     .await?;
 
-    let Some(target_trading_group) = app
-        .trading_groups_reader
-        .get_entity(
-            TradingGroupNoSqlEntity::generate_partition_key(),
-            &target_account.trading_group,
-        )
-        .await
-    else {
-        return Err(TradingExecutorError::TradingGroupNotFound);
-    };
-
-    let Some(target_trading_profile) = app
-        .trading_profiles_reader
-        .get_entity(
-            TradingProfileNoSqlEntity::generate_partition_key(),
-            &target_trading_group.trading_profile_id,
-        )
-        .await
-    else {
-        return Err(TradingExecutorError::TradingProfileNotFound);
+    let Some(open_bid_ask) = requirements.bid_ask else {
+        return Err(TradingExecutorError::NoLiquidity);
     };
-
-    let Some(target_trading_profile_instrument) = target_trading_profile
-        .instruments
-        .iter()
-        .find(|x| x.id == request.asset_pair)
+    // Fail fast checks return the sizing and commission errors before these are read
+    let (Some(invest_amount), Some(commission)) =
+        (validated.invest_amount, requirements.commission)
     else {
-        return Err(TradingExecutorError::TradingProfileInstrumentNotFound);
+        return Err(TradingExecutorError::TechError);
     };
-
-    if !target_trading_profile_instrument
-        .leverages
-        .contains(&request.leverage)
-    {
-        return Err(TradingExecutorError::MultiplierIsNotFound);
-    }
-
-    let delay = delay_open(
-        target_trading_profile_instrument.open_position_min_delay_ms,
-        target_trading_profile_instrument.open_position_max_delay_ms,
-    )
-    .await;
-
-    println!("Open delay: {} ms", delay);
-    sleep(Duration::from_millis(delay as u64)).await;
-
-    if target_trading_profile.is_a_book {
-        let Some(a_book_bridge_grpc_client) = &app.a_book_bridge_grpc_client else {
-            return Err(TradingExecutorError::ABookReject);
-        };
-
-        let side: ABookBridgePositionSide = request.side().into();
-        let a_book_request = ABookBridgeOpenPositionGrpcRequest {
-            instrument_id: request.asset_pair.to_string(),
-            position_id: position_id.to_string(),
-            account_id: request.account_id.to_string(),
-            leverage: request.leverage as f64,
-            invest_amount: request.invest_amount,
-            side: side as i32,
-        };
-
-        let start_date = chrono::Utc::now().to_string();
-        let response = a_book_bridge_grpc_client
-            .open_position(a_book_request.clone(), telemetry_context)
-            .await
-            .unwrap();
-
-        let end_date = chrono::Utc::now().to_string();
-        trade_log::trade_log!(
-            &request.trader_id,
-            &request.account_id,
-            &request.process_id,
-            "n/a",
-            "Calling ABookBridge open position",
-            telemetry_context.clone(),
-            "request" = &a_book_request,
-            "response" = &response,
-            "trace_id" = &telemetry_context.as_string(),
-            "start_date" = &start_date,
-            "end_date" = &end_date
-        );
-
-        let result = {
-            if response.status_code == 0 {
-                Ok(response.position.unwrap())
-            } else {
-                Err(TradingExecutorError::ABookReject)
-            }
-        };
-    }
+    let is_buy = request.side() == TradingExecutorPositionSide::Buy;
+    let target_instrument = validated.instrument;
+    let target_trading_profile = validated.trading_profile;
 
     let balance_update_request = AccountManagerUpdateAccountBalanceGrpcRequest {
         trader_id: request.trader_id.clone(),
         account_id: request.account_id.clone(),
-        delta: -request.invest_amount,
+        delta: -invest_amount,
         comment: "Open position balance charge".to_string(),
         process_id: request.process_id.clone(),
         allow_negative_balance: false,
@@ -170,14 +83,17 @@ pub async fn open_position(
         reference_transaction_id: None,
     };
 
-    let balance_update_result = app
-        .accounts_manager_grpc_client
-        .update_client_account_balance(
-            balance_update_request.clone(),
-            &my_telemetry::MyTelemetryContext::new(),
+    let balance_update_result = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "update_client_account_balance",
+            app.accounts_manager_grpc_client
+                .update_client_account_balance(
+                    balance_update_request.clone(),
+                    &my_telemetry::MyTelemetryContext::new(),
+                ),
         )
-        .await
-        .unwrap();
+        .await?;
 
     trade_log::trade_log!(
         &request.trader_id,
@@ -191,96 +107,293 @@ pub async fn open_position(
         "trace_id" = &telemetry_context.as_string()
     );
 
-    if AccountsManagerOperationResult::Ok != balance_update_result.result() {
-        return Err(TradingExecutorError::NotEnoughBalance);
+    if AccountsManagerOperationResult::Ok as i32 != balance_update_result.result {
+        return Err(TradingExecutorError::from_accounts_manager_code(
+            balance_update_result.result,
+            &downstream,
+        ));
+    }
+
+    if commission > 0.0 {
+        let commission_result = charge_commission(
+            app,
+            &request.trader_id,
+            &request.account_id,
+            format!("{}-commission", request.process_id),
+            -commission,
+            "Open position commission",
+            false,
+            telemetry_context,
+        )
+        .await;
+
+        if let Err(err) = commission_result {
+            refund_open_position_charges(app, &request, invest_amount, 0.0, telemetry_context)
+                .await;
+            return Err(err);
+        }
+    }
+
+    // The hedge goes after the charges: ABookBridge has no close call, so a hedge opened before
+    // a rejected charge could not be undone.
+    if target_trading_profile.is_a_book {
+        let hedge_result = open_a_book_hedge(
+            app,
+            &downstream,
+            &request,
+            &position_id,
+            invest_amount,
+            telemetry_context,
+        )
+        .await;
+
+        if let Err(err) = hedge_result {
+            refund_open_position_charges(
+                app,
+                &request,
+                invest_amount,
+                commission,
+                telemetry_context,
+            )
+            .await;
+            return Err(err);
+        }
     }
 
     let open_position_request = PositionManagerOpenPositionGrpcRequest {
         asset_pair: request.asset_pair.clone(),
         side: request.side,
-        invest_amount: request.invest_amount,
-        leverage: request.leverage as f64,
+        invest_amount,
+        leverage: request.get_leverage(),
         stop_out_percent: target_trading_profile.stop_out_percent,
         process_id: request.process_id.clone(),
         tp_in_profit: request.tp_in_profit,
         sl_in_profit: request.sl_in_profit,
         tp_in_asset_price: request.tp_in_asset_price,
         sl_in_asset_price: request.sl_in_asset_price,
-        open_price: None,
-        open_bid_ask: None,
+        open_price: Some(open_bid_ask.get_open_price(is_buy)),
+        open_bid_ask: Some((&open_bid_ask).into()),
         account_id: request.account_id.clone(),
         trader_id: request.trader_id.clone(),
         base: target_instrument.base.clone(),
         quote: target_instrument.quote.clone(),
         collateral_currency: "USD".to_string(),
-        id: Some(position_id),
+        id: Some(position_id.clone()),
         open_process_id: None,
         metadata: HashMap::new(),
         topping_up_percent: target_trading_profile.topping_up_percent,
         margin_call_percent: target_trading_profile.margin_call_percent,
     };
 
-    let response = match app
-        .position_manager_grpc_client
-        .open_position(open_position_request.clone(), telemetry_context)
-        .await
-    {
-        Ok(response) => {
+    let response = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "open_position",
+            app.position_manager_grpc_client
+                .open_position(open_position_request.clone(), telemetry_context),
+        )
+        .await;
+
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => {
             trade_log::trade_log!(
                 &request.trader_id,
                 &request.account_id,
                 &request.process_id,
                 "n/a",
-                "Success open position request.",
+                "Failed to open position. Returning charged funds.",
                 telemetry_context.clone(),
                 "request" = &open_position_request,
-                "response" = &response,
+                "err" = &format!("{:?}", err),
                 "trace_id" = &telemetry_context.as_string()
             );
-            
-            let position: TradingExecutorActivePositionGrpcModel =
-                response.position.unwrap().into();
 
-            Ok(position)
+            refund_open_position_charges(
+                app,
+                &request,
+                invest_amount,
+                commission,
+                telemetry_context,
+            )
+            .await;
+
+            if target_trading_profile.is_a_book {
+                log_unbacked_a_book_hedge(&request, &position_id, telemetry_context);
+            }
+
+            return Err(err);
         }
-        Err(err) => {
-            let return_request = AccountManagerUpdateAccountBalanceGrpcRequest {
-                trader_id: request.trader_id.clone(),
-                account_id: request.account_id.clone(),
-                delta: request.invest_amount,
-                comment: "Cancel open position balance charge".to_string(),
-                process_id: Uuid::new_v4().to_string(),
-                allow_negative_balance: false,
-                reason: UpdateBalanceReason::TradingResult as i32,
-                reference_transaction_id: None,
-            };
+    };
 
+    trade_log::trade_log!(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        "n/a",
+        "Success open position request.",
+        telemetry_context.clone(),
+        "request" = &open_position_request,
+        "response" = &response,
+        "trace_id" = &telemetry_context.as_string()
+    );
+
+    let Some(position) = response.position else {
+        refund_open_position_charges(app, &request, invest_amount, commission, telemetry_context)
+            .await;
+
+        if target_trading_profile.is_a_book {
+            log_unbacked_a_book_hedge(&request, &position_id, telemetry_context);
+        }
+
+        return Err(TradingExecutorError::from_position_manager_code(
+            response.status,
+            &downstream,
+        ));
+    };
+
+    Ok((position.into(), commission))
+}
+
+async fn refund_open_position_charges(
+    app: &Arc<AppContext>,
+    request: &TradingExecutorOpenPositionGrpcRequest,
+    invest_amount: f64,
+    commission: f64,
+    telemetry_context: &MyTelemetryContext,
+) {
+    let return_request = AccountManagerUpdateAccountBalanceGrpcRequest {
+        trader_id: request.trader_id.clone(),
+        account_id: request.account_id.clone(),
+        delta: invest_amount,
+        comment: "Cancel open position balance charge".to_string(),
+        process_id: Uuid::new_v4().to_string(),
+        allow_negative_balance: false,
+        reason: UpdateBalanceReason::TradingResult as i32,
+        reference_transaction_id: None,
+    };
+
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        telemetry_context,
+    );
+
+    // Nothing to propagate the failure to: the open is already rejected. The failed call is
+    // recorded in the trade log for manual reconciliation.
+    let _ = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "update_client_account_balance",
             app.accounts_manager_grpc_client
                 .update_client_account_balance(
                     return_request.clone(),
                     &my_telemetry::MyTelemetryContext::new(),
-                )
-                .await
-                .unwrap();
+                ),
+        )
+        .await;
 
-            trade_log::trade_log!(
-                &request.trader_id,
-                &request.account_id,
-                &request.process_id,
-                "n/a",
-                "Failed to open position. Returning charged funds.",
-                telemetry_context.clone(),
-                "request" = &open_position_request,
-                "err" = &format!("{:?}", err),
-                "balance_return_request" = &return_request,
-                "trace_id" = &telemetry_context.as_string()
-            );
+    trade_log::trade_log!(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        "n/a",
+        "Returned open position balance charge.",
+        telemetry_context.clone(),
+        "balance_return_request" = &return_request,
+        "trace_id" = &telemetry_context.as_string()
+    );
 
-            return Err(TradingExecutorError::TechError);
-        }
+    if commission > 0.0 {
+        let _ = charge_commission(
+            app,
+            &request.trader_id,
+            &request.account_id,
+            Uuid::new_v4().to_string(),
+            commission,
+            "Cancel open position commission",
+            true,
+            telemetry_context,
+        )
+        .await;
+    }
+}
+
+async fn open_a_book_hedge(
+    app: &Arc<AppContext>,
+    downstream: &DownstreamContext<'_>,
+    request: &TradingExecutorOpenPositionGrpcRequest,
+    position_id: &str,
+    invest_amount: f64,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<(), TradingExecutorError> {
+    let Some(a_book_bridge_grpc_client) = &app.a_book_bridge_grpc_client else {
+        return Err(TradingExecutorError::ABookReject);
     };
 
-    return response;
+    let side: ABookBridgePositionSide = request.side().into();
+    let a_book_request = ABookBridgeOpenPositionGrpcRequest {
+        instrument_id: request.asset_pair.to_string(),
+        position_id: position_id.to_string(),
+        account_id: request.account_id.to_string(),
+        leverage: request.get_leverage(),
+        invest_amount,
+        side: side as i32,
+    };
+
+    let start_date = chrono::Utc::now().to_string();
+    let response = downstream
+        .call(
+            DownstreamService::ABookBridge,
+            "open_position",
+            a_book_bridge_grpc_client.open_position(a_book_request.clone(), telemetry_context),
+        )
+        .await?;
+
+    let end_date = chrono::Utc::now().to_string();
+    trade_log::trade_log!(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        "n/a",
+        "Calling ABookBridge open position",
+        telemetry_context.clone(),
+        "request" = &a_book_request,
+        "response" = &response,
+        "trace_id" = &telemetry_context.as_string(),
+        "start_date" = &start_date,
+        "end_date" = &end_date
+    );
+
+    // A rejected hedge stops the open, the caller returns the charges
+    if response.status_code != OpenPositionGrpcResponseStatusCode::Success as i32 {
+        return Err(TradingExecutorError::from_a_book_bridge_code(
+            response.status_code,
+            downstream,
+        ));
+    }
+
+    Ok(())
+}
+
+/// ABookBridge has no close call, so a hedge left after a failed Position Manager open is
+/// recorded for manual reconciliation.
+fn log_unbacked_a_book_hedge(
+    request: &TradingExecutorOpenPositionGrpcRequest,
+    position_id: &str,
+    telemetry_context: &MyTelemetryContext,
+) {
+    trade_log::trade_log!(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        "n/a",
+        "A-book hedge is left without a client position.",
+        telemetry_context.clone(),
+        "position_id" = &position_id.to_string(),
+        "trace_id" = &telemetry_context.as_string()
+    );
 }
 
 async fn delay_open(from: i32, to: i32) -> i32 {
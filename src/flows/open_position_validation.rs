@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use my_nosql_contracts::{
+    TradingGroupNoSqlEntity, TradingInstrumentNoSqlEntity, TradingProfileNoSqlEntity,
+};
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{
+    accounts_manager_grpc::{AccountGrpcModel, AccountManagerGetClientAccountGrpcRequest},
+    calculate_account_margin, calculate_commission, get_allowed_leverages, get_executable_bid_ask,
+    get_open_min_margin_level, resolve_invest_amount,
+    trading_executor_grpc::{TradingExecutorOpenPositionGrpcRequest, TradingExecutorPositionSide},
+    validate_instrument_day_off, validate_instrument_pre_close, validate_leverage,
+    validate_leverage_tier, validate_market_liquidity, validate_open_margin, AppContext,
+    DownstreamContext, DownstreamService, ExecutableBidAsk, OrderSize, TradingExecutorError,
+    TradingOperationType,
+};
+
+/// Decides what happens with a failed open check. The open flow stops on the first failure,
+/// the dry run records it and goes on. Missing entities stop both.
+pub trait OpenPositionChecks {
+    fn check(
+        &mut self,
+        result: Result<(), TradingExecutorError>,
+    ) -> Result<(), TradingExecutorError>;
+}
+
+pub struct FailFastChecks;
+
+impl OpenPositionChecks for FailFastChecks {
+    fn check(
+        &mut self,
+        result: Result<(), TradingExecutorError>,
+    ) -> Result<(), TradingExecutorError> {
+        result
+    }
+}
+
+pub struct ValidatedOpenPosition {
+    pub instrument: Arc<TradingInstrumentNoSqlEntity>,
+    pub account: AccountGrpcModel,
+    pub trading_profile_id: String,
+    pub trading_profile: Arc<TradingProfileNoSqlEntity>,
+    /// `None` only when the dry run recorded a failed sizing check.
+    pub invest_amount: Option<f64>,
+    pub open_position_min_delay_ms: i32,
+    pub open_position_max_delay_ms: i32,
+}
+
+pub struct OpenPositionRequirements {
+    pub bid_ask: Option<ExecutableBidAsk>,
+    /// `None` when the invest amount is missing or the commission check failed in a dry run.
+    pub commission: Option<f64>,
+}
+
+/// Checks done before the execution delay: market state, trading profile, leverage and the
+/// order size.
+pub async fn validate_open_position_request(
+    app: &AppContext,
+    request: &TradingExecutorOpenPositionGrpcRequest,
+    downstream: &DownstreamContext<'_>,
+    checks: &mut impl OpenPositionChecks,
+) -> Result<ValidatedOpenPosition, TradingExecutorError> {
+    let Some(instrument) = app
+        .trading_instruments_reader
+        .get_entity(
+            TradingInstrumentNoSqlEntity::generate_partition_key(),
+            &request.asset_pair,
+        )
+        .await
+    else {
+        return Err(TradingExecutorError::InstrumentNotFound);
+    };
+
+    // Day off checks don't depend on the account, so they run before the downstream call
+    if app
+        .market_state_policy
+        .get_checks(TradingOperationType::Open)
+        .day_off
+    {
+        checks.check(validate_instrument_day_off(app, &instrument).await)?;
+        checks.check(validate_instrument_pre_close(app, &instrument).await)?;
+    }
+
+    let Some(account) = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "get_client_account",
+            app.accounts_manager_grpc_client.get_client_account(
+                AccountManagerGetClientAccountGrpcRequest {
+                    trader_id: request.trader_id.clone(),
+                    account_id: request.account_id.clone(),
+                },
+                downstream.telemetry_context,
+            ),
+        )
+        .await?
+        .account
+    else {
+        return Err(TradingExecutorError::AccountNotFound);
+    };
+
+    checks.check(
+        validate_market_liquidity(
+            app,
+            downstream,
+            TradingOperationType::Open,
+            &instrument,
+            &account.currency,
+        )
+        .await,
+    )?;
+
+    let Some(trading_group) = app
+        .trading_groups_reader
+        .get_entity(
+            TradingGroupNoSqlEntity::generate_partition_key(),
+            &account.trading_group,
+        )
+        .await
+    else {
+        return Err(TradingExecutorError::TradingGroupNotFound);
+    };
+
+    let Some(trading_profile) = app
+        .trading_profiles_reader
+        .get_entity(
+            TradingProfileNoSqlEntity::generate_partition_key(),
+            &trading_group.trading_profile_id,
+        )
+        .await
+    else {
+        return Err(TradingExecutorError::TradingProfileNotFound);
+    };
+
+    let Some(trading_profile_instrument) = trading_profile
+        .instruments
+        .iter()
+        .find(|x| x.id == request.asset_pair)
+    else {
+        return Err(TradingExecutorError::TradingProfileInstrumentNotFound);
+    };
+
+    let allowed_leverages = get_allowed_leverages(
+        app,
+        &request.account_id,
+        &trading_group.trading_profile_id,
+        &request.asset_pair,
+        &trading_profile_instrument.leverages,
+    )
+    .await;
+
+    checks.check(validate_leverage(
+        &allowed_leverages,
+        request.get_leverage(),
+    ))?;
+
+    let order_size = match OrderSize::new(
+        request.sizing_mode(),
+        request.invest_amount,
+        request.sizing_value,
+        request.side() == TradingExecutorPositionSide::Buy,
+        request.sl_in_asset_price,
+    ) {
+        Ok(order_size) => Some(order_size),
+        Err(error) => {
+            checks.check(Err(error))?;
+            None
+        }
+    };
+
+    let invest_amount = match order_size {
+        Some(order_size) => match resolve_invest_amount(
+            app,
+            order_size,
+            request.get_leverage(),
+            None,
+            &instrument,
+            &account,
+            &trading_group.trading_profile_id,
+            downstream.telemetry_context,
+        )
+        .await
+        {
+            Ok(invest_amount) => Some(invest_amount),
+            Err(error) => {
+                checks.check(Err(error))?;
+                None
+            }
+        },
+        None => None,
+    };
+
+    // The leverage tier is picked by the invest amount, so it is skipped without one
+    if let Some(invest_amount) = invest_amount {
+        checks.check(
+            validate_leverage_tier(
+                app,
+                downstream,
+                &trading_group.trading_profile_id,
+                &request.asset_pair,
+                request.get_leverage(),
+                invest_amount,
+            )
+            .await,
+        )?;
+    }
+
+    Ok(ValidatedOpenPosition {
+        open_position_min_delay_ms: trading_profile_instrument.open_position_min_delay_ms,
+        open_position_max_delay_ms: trading_profile_instrument.open_position_max_delay_ms,
+        instrument,
+        account,
+        trading_profile_id: trading_group.trading_profile_id.clone(),
+        trading_profile,
+        invest_amount,
+    })
+}
+
+/// Checks done at execution price: commission, margin and the A-book route. The open flow runs
+/// them after the execution delay. Commission and margin are skipped without an invest amount.
+pub async fn validate_open_position_requirements(
+    app: &AppContext,
+    request: &TradingExecutorOpenPositionGrpcRequest,
+    validated: &ValidatedOpenPosition,
+    checks: &mut impl OpenPositionChecks,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<OpenPositionRequirements, TradingExecutorError> {
+    let bid_ask =
+        match get_executable_bid_ask(app, &validated.trading_profile_id, &request.asset_pair).await
+        {
+            Ok(bid_ask) => Some(bid_ask),
+            Err(error) => {
+                checks.check(Err(error))?;
+                None
+            }
+        };
+
+    let mut commission = None;
+
+    if let Some(invest_amount) = validated.invest_amount {
+        commission = match calculate_commission(
+            app,
+            &validated.trading_profile_id,
+            &validated.instrument,
+            &validated.account.currency,
+            invest_amount * request.get_leverage(),
+        )
+        .await
+        {
+            Ok(commission) => Some(commission),
+            Err(error) => {
+                checks.check(Err(error))?;
+                None
+            }
+        };
+
+        let min_margin_level = get_open_min_margin_level(
+            app,
+            &validated.trading_profile_id,
+            &validated.trading_profile,
+            &request.asset_pair,
+        )
+        .await;
+
+        let account_margin = calculate_account_margin(
+            app,
+            &validated.account,
+            &validated.trading_profile_id,
+            telemetry_context,
+        )
+        .await;
+
+        match (account_margin, min_margin_level) {
+            (Ok((account_margin, _)), Ok(min_margin_level)) => {
+                checks.check(validate_open_margin(
+                    &account_margin,
+                    invest_amount,
+                    commission.unwrap_or(0.0),
+                    min_margin_level,
+                ))?
+            }
+            (account_margin, min_margin_level) => {
+                checks.check(account_margin.map(|_| ()))?;
+                checks.check(min_margin_level.map(|_| ()))?;
+            }
+        }
+    }
+
+    if validated.trading_profile.is_a_book && app.a_book_bridge_grpc_client.is_none() {
+        checks.check(Err(TradingExecutorError::ABookReject))?;
+    }
+
+    Ok(OpenPositionRequirements {
+        bid_ask,
+        commission,
+    })
+}
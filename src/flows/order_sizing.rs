@@ -0,0 +1,377 @@
+use my_nosql_contracts::TradingInstrumentNoSqlEntity;
+use service_sdk::my_telemetry::MyTelemetryContext;
+
+use crate::{
+    accounts_manager_grpc::AccountGrpcModel, calculate_account_margin, get_executable_bid_ask,
+    resolve_cross_rate, trading_executor_grpc::TradingExecutorSizingMode, AppContext,
+    InstrumentTradingSettingsNoSqlEntity, TradingExecutorError,
+};
+
+/// Order size as requested by the client. Everything except `InvestAmount` is converted to the
+/// invest amount in collateral currency before the order goes to the Position Manager.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderSize {
+    InvestAmount(f64),
+    /// Position notional in collateral currency.
+    Notional(f64),
+    /// Units of the instrument base asset.
+    Units(f64),
+    Lots(f64),
+    FreeMarginPercent(f64),
+    /// Percent of the balance lost if the stop loss is hit.
+    RiskPercent {
+        percent: f64,
+        stop_loss_price: f64,
+        is_buy: bool,
+    },
+}
+
+impl OrderSize {
+    pub fn new(
+        sizing_mode: TradingExecutorSizingMode,
+        invest_amount: f64,
+        sizing_value: Option<f64>,
+        is_buy: bool,
+        sl_in_asset_price: Option<f64>,
+    ) -> Result<Self, TradingExecutorError> {
+        if sizing_mode == TradingExecutorSizingMode::InvestAmount {
+            return Ok(Self::InvestAmount(invest_amount));
+        }
+
+        let Some(value) = sizing_value else {
+            return Err(TradingExecutorError::InvalidSizingValue { value: None });
+        };
+
+        if !value.is_finite() || value <= 0.0 {
+            return Err(TradingExecutorError::InvalidSizingValue { value: Some(value) });
+        }
+
+        let result = match sizing_mode {
+            TradingExecutorSizingMode::InvestAmount => Self::InvestAmount(value),
+            TradingExecutorSizingMode::Notional => Self::Notional(value),
+            TradingExecutorSizingMode::Units => Self::Units(value),
+            TradingExecutorSizingMode::Lots => Self::Lots(value),
+            TradingExecutorSizingMode::FreeMarginPercent => {
+                validate_percent(value)?;
+                Self::FreeMarginPercent(value)
+            }
+            TradingExecutorSizingMode::RiskPercent => {
+                validate_percent(value)?;
+
+                let Some(stop_loss_price) = sl_in_asset_price else {
+                    return Err(TradingExecutorError::StopLossRequired);
+                };
+
+                Self::RiskPercent {
+                    percent: value,
+                    stop_loss_price,
+                    is_buy,
+                }
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+fn validate_percent(value: f64) -> Result<(), TradingExecutorError> {
+    if value > 100.0 {
+        return Err(TradingExecutorError::OperationIsTooHigh {
+            amount: value,
+            max_amount: 100.0,
+        });
+    }
+
+    Ok(())
+}
+
+/// Market data the conversion depends on. Only the fields required by the sizing mode are
+/// resolved, so e.g. invest amount orders never touch the rates or the account margin.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrderSizingRates {
+    /// Price of one unit of the instrument base asset in collateral currency.
+    pub base_collateral_rate: f64,
+    pub contract_size: f64,
+    pub free_margin: f64,
+    pub balance: f64,
+    /// Executable price the position would be opened at.
+    pub entry_price: f64,
+}
+
+pub fn get_invest_amount(
+    order_size: OrderSize,
+    leverage: f64,
+    rates: &OrderSizingRates,
+) -> Result<f64, TradingExecutorError> {
+    let invest_amount = match order_size {
+        OrderSize::InvestAmount(invest_amount) => invest_amount,
+        OrderSize::Notional(notional) => notional / leverage,
+        OrderSize::Units(units) => units * rates.base_collateral_rate / leverage,
+        OrderSize::Lots(lots) => lots * rates.contract_size * rates.base_collateral_rate / leverage,
+        OrderSize::FreeMarginPercent(percent) => rates.free_margin * percent / 100.0,
+        OrderSize::RiskPercent {
+            percent,
+            stop_loss_price,
+            is_buy,
+        } => {
+            let stop_distance = match is_buy {
+                true => rates.entry_price - stop_loss_price,
+                false => stop_loss_price - rates.entry_price,
+            };
+
+            if stop_distance <= 0.0 {
+                return Err(TradingExecutorError::SlIsTooClose);
+            }
+
+            // The loss at the stop is notional * distance / entry price, so the quote to
+            // collateral rate cancels out and only the relative distance matters.
+            let risk_amount = rates.balance * percent / 100.0;
+            risk_amount * rates.entry_price / stop_distance / leverage
+        }
+    };
+
+    Ok(invest_amount)
+}
+
+pub fn validate_invest_amount(
+    invest_amount: f64,
+    min_invest_amount: Option<f64>,
+    max_invest_amount: Option<f64>,
+) -> Result<(), TradingExecutorError> {
+    let min_invest_amount = min_invest_amount.unwrap_or(0.0);
+
+    if invest_amount <= 0.0 || invest_amount < min_invest_amount {
+        return Err(TradingExecutorError::OperationIsTooLow {
+            amount: invest_amount,
+            min_amount: min_invest_amount,
+        });
+    }
+
+    if let Some(max_invest_amount) = max_invest_amount {
+        if invest_amount > max_invest_amount {
+            return Err(TradingExecutorError::OperationIsTooHigh {
+                amount: invest_amount,
+                max_amount: max_invest_amount,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Lot based sizing and commission can't guess the contract size, so a missing or non-positive
+/// setting rejects the operation.
+pub async fn get_contract_size(
+    app: &AppContext,
+    instrument: &TradingInstrumentNoSqlEntity,
+) -> Result<f64, TradingExecutorError> {
+    app.instrument_trading_settings_reader
+        .get_entity(
+            InstrumentTradingSettingsNoSqlEntity::generate_partition_key(),
+            instrument.get_id(),
+        )
+        .await
+        .and_then(|x| x.get_contract_size())
+        .ok_or(TradingExecutorError::ContractSizeNotConfigured)
+}
+
+/// Converts the order size to the invest amount and checks it against the trading profile
+/// instrument limits. Pending orders pass their desired price as `entry_price`; market orders
+/// use the current executable price.
+pub async fn resolve_invest_amount(
+    app: &AppContext,
+    order_size: OrderSize,
+    leverage: f64,
+    entry_price: Option<f64>,
+    instrument: &TradingInstrumentNoSqlEntity,
+    account: &AccountGrpcModel,
+    trading_profile_id: &str,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<f64, TradingExecutorError> {
+    let mut rates = OrderSizingRates::default();
+
+    match order_size {
+        OrderSize::InvestAmount(_) | OrderSize::Notional(_) => {}
+        OrderSize::Units(_) | OrderSize::Lots(_) => {
+            if let OrderSize::Lots(_) = order_size {
+                rates.contract_size = get_contract_size(app, instrument).await?;
+            }
+
+            rates.base_collateral_rate =
+                resolve_cross_rate(app, &instrument.base, &account.currency)
+                    .await?
+                    .rate;
+        }
+        OrderSize::FreeMarginPercent(_) => {
+            let (margin, _) =
+                calculate_account_margin(app, account, trading_profile_id, telemetry_context)
+                    .await?;
+
+            rates.free_margin = margin.free_margin;
+        }
+        OrderSize::RiskPercent { is_buy, .. } => {
+            rates.balance = account.balance;
+            rates.entry_price = match entry_price {
+                Some(entry_price) => entry_price,
+                None => get_executable_bid_ask(app, trading_profile_id, instrument.get_id())
+                    .await?
+                    .get_open_price(is_buy),
+            };
+        }
+    }
+
+    let invest_amount = get_invest_amount(order_size, leverage, &rates)?;
+
+    let profile_instrument_settings = app
+        .trading_profile_instrument_settings_reader
+        .get_entity(trading_profile_id, instrument.get_id())
+        .await;
+
+    validate_invest_amount(
+        invest_amount,
+        profile_instrument_settings
+            .as_ref()
+            .and_then(|x| x.min_invest_amount),
+        profile_instrument_settings
+            .as_ref()
+            .and_then(|x| x.max_invest_amount),
+    )?;
+
+    Ok(invest_amount)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{trading_executor_grpc::TradingExecutorSizingMode, TradingExecutorError};
+
+    use super::{get_invest_amount, validate_invest_amount, OrderSize, OrderSizingRates};
+
+    const RATES: OrderSizingRates = OrderSizingRates {
+        base_collateral_rate: 1.1,
+        contract_size: 100_000.0,
+        free_margin: 2_000.0,
+        balance: 10_000.0,
+        entry_price: 1.1,
+    };
+
+    #[test]
+    fn convert_order_size_to_invest_amount() {
+        let cases = [
+            (OrderSize::InvestAmount(50.0), 50.0),
+            (OrderSize::Notional(11_000.0), 110.0),
+            (OrderSize::Units(10_000.0), 110.0),
+            (OrderSize::Lots(0.1), 110.0),
+            (OrderSize::FreeMarginPercent(25.0), 500.0),
+        ];
+
+        for (order_size, expected) in cases {
+            let invest_amount = get_invest_amount(order_size, 100.0, &RATES).unwrap();
+
+            assert!(
+                (invest_amount - expected).abs() < 1e-9,
+                "{:?}: {}",
+                order_size,
+                invest_amount
+            );
+        }
+    }
+
+    #[test]
+    fn risk_percent_from_stop_distance() {
+        // 1% of 10 000 at a 100 pip stop: 100 / 0.01 = 10 000 units, 11 000 notional
+        let order_size = OrderSize::RiskPercent {
+            percent: 1.0,
+            stop_loss_price: 1.09,
+            is_buy: true,
+        };
+
+        let invest_amount = get_invest_amount(order_size, 100.0, &RATES).unwrap();
+        assert!((invest_amount - 110.0).abs() < 1e-6);
+
+        let wrong_side = OrderSize::RiskPercent {
+            percent: 1.0,
+            stop_loss_price: 1.09,
+            is_buy: false,
+        };
+
+        assert_eq!(true, get_invest_amount(wrong_side, 100.0, &RATES).is_err());
+    }
+
+    #[test]
+    fn reject_missing_or_invalid_sizing_value() {
+        assert_eq!(
+            true,
+            matches!(
+                OrderSize::new(TradingExecutorSizingMode::Lots, 0.0, None, true, None),
+                Err(TradingExecutorError::InvalidSizingValue { value: None })
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                OrderSize::new(
+                    TradingExecutorSizingMode::Units,
+                    0.0,
+                    Some(-1.0),
+                    true,
+                    None
+                ),
+                Err(TradingExecutorError::InvalidSizingValue { value: Some(_) })
+            )
+        );
+        assert_eq!(
+            true,
+            OrderSize::new(
+                TradingExecutorSizingMode::FreeMarginPercent,
+                0.0,
+                Some(150.0),
+                true,
+                None
+            )
+            .is_err()
+        );
+        assert_eq!(
+            true,
+            matches!(
+                OrderSize::new(
+                    TradingExecutorSizingMode::RiskPercent,
+                    0.0,
+                    Some(1.0),
+                    true,
+                    None
+                ),
+                Err(TradingExecutorError::StopLossRequired)
+            )
+        );
+        assert_eq!(
+            Ok(OrderSize::InvestAmount(10.0)),
+            OrderSize::new(
+                TradingExecutorSizingMode::InvestAmount,
+                10.0,
+                None,
+                true,
+                None
+            )
+            .map_err(|_| ())
+        );
+    }
+
+    #[test]
+    fn invest_amount_limits() {
+        match validate_invest_amount(5.0, Some(10.0), Some(1_000.0)) {
+            Err(TradingExecutorError::OperationIsTooLow { min_amount, .. }) => {
+                assert_eq!(10.0, min_amount)
+            }
+            _ => panic!("Invest amount must be too low"),
+        }
+
+        match validate_invest_amount(5_000.0, Some(10.0), Some(1_000.0)) {
+            Err(TradingExecutorError::OperationIsTooHigh { max_amount, .. }) => {
+                assert_eq!(1_000.0, max_amount)
+            }
+            _ => panic!("Invest amount must be too high"),
+        }
+
+        assert_eq!(true, validate_invest_amount(100.0, None, None).is_ok());
+    }
+}
@@ -0,0 +1,339 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use service_sdk::my_telemetry::MyTelemetryContext;
+use tokio::sync::mpsc;
+
+use crate::{
+    get_account_trading_profile_id,
+    position_manager_grpc::{
+        PositionManagerActivePositionGrpcModel, PositionManagerGetActivePositionsGrpcRequest,
+    },
+    revalue_position,
+    trading_executor_grpc::{
+        TradingExecutorPositionEventGrpcModel, TradingExecutorPositionEventType,
+        TradingExecutorPositionPnlGrpcModel, TradingExecutorSubscribeAccountPositionsGrpcRequest,
+    },
+    ActivePositionsSender, ActivePositionsSnapshot, AppContext, DownstreamContext,
+    DownstreamService, TradingExecutorError,
+};
+
+const POSITIONS_STREAM_BUFFER: usize = 1024;
+const POSITIONS_POLL_INTERVAL_MS: u64 = 1000;
+const DEFAULT_PNL_REFRESH_INTERVAL_MS: u64 = 1000;
+const MIN_PNL_REFRESH_INTERVAL_MS: u64 = 250;
+
+/// Streams position lifecycle events for an account. The Position Manager has no event feed,
+/// so one poller per account re-reads active positions and every subscriber diffs the
+/// snapshots it receives. The first snapshot is sent as `Snapshot` events, a single one without
+/// a position for an account without positions. PnL refreshes are computed from the latest
+/// snapshot at the subscriber's own interval.
+pub async fn subscribe_account_positions(
+    app: &Arc<AppContext>,
+    request: TradingExecutorSubscribeAccountPositionsGrpcRequest,
+    telemetry_context: &MyTelemetryContext,
+) -> Result<
+    mpsc::Receiver<Result<TradingExecutorPositionEventGrpcModel, tonic::Status>>,
+    TradingExecutorError,
+> {
+    let trading_profile_id = get_account_trading_profile_id(
+        app,
+        &request.trader_id,
+        &request.account_id,
+        telemetry_context,
+    )
+    .await?;
+
+    let refresh_interval = request
+        .pnl_refresh_interval_milliseconds
+        .unwrap_or(DEFAULT_PNL_REFRESH_INTERVAL_MS)
+        .max(MIN_PNL_REFRESH_INTERVAL_MS);
+
+    let (mut snapshots, poller) = app
+        .account_positions_pollers
+        .subscribe(&request.trader_id, &request.account_id)
+        .await;
+
+    if let Some(poller) = poller {
+        tokio::spawn(poll_account_positions(
+            app.clone(),
+            request.trader_id.clone(),
+            request.account_id.clone(),
+            poller,
+        ));
+    }
+
+    let (sender, receiver) = mpsc::channel(POSITIONS_STREAM_BUFFER);
+    let app = app.clone();
+
+    tokio::spawn(async move {
+        let mut previous: Option<HashMap<String, PositionManagerActivePositionGrpcModel>> = None;
+        let mut pnl_interval = tokio::time::interval(Duration::from_millis(refresh_interval));
+        let mut latest = snapshots.borrow_and_update().clone();
+
+        loop {
+            if let Some(positions) = latest.take() {
+                let events = get_position_events(previous.as_ref(), &positions);
+
+                for event in events {
+                    if sender.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+
+                previous = Some(
+                    positions
+                        .iter()
+                        .map(|x| (x.id.clone(), x.clone()))
+                        .collect(),
+                );
+            }
+
+            tokio::select! {
+                changed = snapshots.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+
+                    latest = snapshots.borrow_and_update().clone();
+                }
+                _ = pnl_interval.tick() => {
+                    if sender.is_closed() {
+                        return;
+                    }
+
+                    let Some(positions) = &previous else {
+                        continue;
+                    };
+
+                    let event = get_pnl_event(&app, positions, &trading_profile_id).await;
+
+                    if let Some(event) = event {
+                        if sender.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(receiver)
+}
+
+async fn poll_account_positions(
+    app: Arc<AppContext>,
+    trader_id: String,
+    account_id: String,
+    sender: ActivePositionsSender,
+) {
+    let mut interval = tokio::time::interval(Duration::from_millis(POSITIONS_POLL_INTERVAL_MS));
+
+    loop {
+        interval.tick().await;
+
+        if !app
+            .account_positions_pollers
+            .keep_polling(&trader_id, &account_id)
+            .await
+        {
+            return;
+        }
+
+        let telemetry_context = MyTelemetryContext::new();
+        let downstream = DownstreamContext::new(&trader_id, &account_id, "n/a", &telemetry_context);
+
+        let positions = downstream
+            .call(
+                DownstreamService::PositionManager,
+                "get_account_active_positions",
+                app.position_manager_grpc_client
+                    .get_account_active_positions(
+                        PositionManagerGetActivePositionsGrpcRequest {
+                            trader_id: trader_id.clone(),
+                            account_id: account_id.clone(),
+                        },
+                        &telemetry_context,
+                    ),
+            )
+            .await;
+
+        // A failed poll is retried on the next tick; subscribers keep the last state.
+        let Ok(positions) = positions else {
+            continue;
+        };
+
+        sender.send_replace(Some(Arc::new(positions.unwrap_or_default())));
+    }
+}
+
+fn get_position_events(
+    previous: Option<&HashMap<String, PositionManagerActivePositionGrpcModel>>,
+    positions: &ActivePositionsSnapshot,
+) -> Vec<TradingExecutorPositionEventGrpcModel> {
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+
+    let events = match previous {
+        Some(previous) => diff_positions(previous, positions),
+        None if positions.is_empty() => {
+            // Sent so the client can tell an empty account from a snapshot that is still loading
+            return vec![TradingExecutorPositionEventGrpcModel {
+                r#type: TradingExecutorPositionEventType::Snapshot as i32,
+                position: None,
+                pnl: vec![],
+                unix_timestamp_milliseconds: now,
+            }];
+        }
+        None => positions
+            .iter()
+            .map(|x| (TradingExecutorPositionEventType::Snapshot, x.clone()))
+            .collect(),
+    };
+
+    events
+        .into_iter()
+        .map(
+            |(event_type, position)| TradingExecutorPositionEventGrpcModel {
+                r#type: event_type as i32,
+                position: Some(position.into()),
+                pnl: vec![],
+                unix_timestamp_milliseconds: now,
+            },
+        )
+        .collect()
+}
+
+async fn get_pnl_event(
+    app: &AppContext,
+    positions: &HashMap<String, PositionManagerActivePositionGrpcModel>,
+    trading_profile_id: &str,
+) -> Option<TradingExecutorPositionEventGrpcModel> {
+    if positions.is_empty() {
+        return None;
+    }
+
+    let mut pnl = Vec::with_capacity(positions.len());
+
+    for position in positions.values() {
+        pnl.push(TradingExecutorPositionPnlGrpcModel {
+            position_id: position.id.clone(),
+            pnl: revalue_position(app, position, trading_profile_id).await,
+        });
+    }
+
+    Some(TradingExecutorPositionEventGrpcModel {
+        r#type: TradingExecutorPositionEventType::PnlRefresh as i32,
+        position: None,
+        pnl,
+        unix_timestamp_milliseconds: chrono::Utc::now().timestamp_millis() as u64,
+    })
+}
+
+/// Compares two snapshots of active positions. Closed positions are reported with their last
+/// known state.
+pub fn diff_positions(
+    previous: &HashMap<String, PositionManagerActivePositionGrpcModel>,
+    current: &[PositionManagerActivePositionGrpcModel],
+) -> Vec<(
+    TradingExecutorPositionEventType,
+    PositionManagerActivePositionGrpcModel,
+)> {
+    let mut result = vec![];
+
+    for position in current {
+        match previous.get(&position.id) {
+            Some(previous_position) => {
+                if is_position_updated(previous_position, position) {
+                    result.push((TradingExecutorPositionEventType::Updated, position.clone()));
+                }
+            }
+            None => result.push((TradingExecutorPositionEventType::Opened, position.clone())),
+        }
+    }
+
+    for (id, position) in previous {
+        if !current.iter().any(|x| &x.id == id) {
+            result.push((TradingExecutorPositionEventType::Closed, position.clone()));
+        }
+    }
+
+    result
+}
+
+/// Profit changes on every price tick and is covered by PnL refreshes, so only changes made
+/// by an operation on the position count as an update.
+fn is_position_updated(
+    previous: &PositionManagerActivePositionGrpcModel,
+    current: &PositionManagerActivePositionGrpcModel,
+) -> bool {
+    previous.last_update_process_id != current.last_update_process_id
+        || previous.invest_amount != current.invest_amount
+        || previous.tp_in_profit != current.tp_in_profit
+        || previous.sl_in_profit != current.sl_in_profit
+        || previous.tp_in_asset_price != current.tp_in_asset_price
+        || previous.sl_in_asset_price != current.sl_in_asset_price
+        || previous.swaps.len() != current.swaps.len()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use crate::{
+        position_manager_grpc::PositionManagerActivePositionGrpcModel,
+        trading_executor_grpc::TradingExecutorPositionEventType,
+    };
+
+    use super::{diff_positions, get_position_events};
+
+    fn position(id: &str) -> PositionManagerActivePositionGrpcModel {
+        PositionManagerActivePositionGrpcModel {
+            id: id.to_string(),
+            last_update_process_id: "open".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diff_opened_updated_and_closed() {
+        let previous: HashMap<String, PositionManagerActivePositionGrpcModel> =
+            vec![position("1"), position("2"), position("3")]
+                .into_iter()
+                .map(|x| (x.id.clone(), x))
+                .collect();
+
+        let mut updated = position("2");
+        updated.sl_in_asset_price = Some(1.05);
+        let mut repriced = position("3");
+        repriced.profit = 12.0;
+
+        let current = vec![updated, repriced, position("4")];
+
+        let mut events: Vec<(TradingExecutorPositionEventType, String)> =
+            diff_positions(&previous, &current)
+                .into_iter()
+                .map(|(event_type, position)| (event_type, position.id))
+                .collect();
+        events.sort_by(|a, b| a.1.cmp(&b.1));
+
+        assert_eq!(
+            vec![
+                (TradingExecutorPositionEventType::Closed, "1".to_string()),
+                (TradingExecutorPositionEventType::Updated, "2".to_string()),
+                (TradingExecutorPositionEventType::Opened, "4".to_string()),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn empty_account_gets_snapshot_event() {
+        let events = get_position_events(None, &Arc::new(vec![]));
+
+        assert_eq!(1, events.len());
+        assert_eq!(
+            TradingExecutorPositionEventType::Snapshot as i32,
+            events[0].r#type
+        );
+        assert_eq!(true, events[0].position.is_none());
+    }
+}
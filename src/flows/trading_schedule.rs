@@ -0,0 +1,279 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use my_nosql_contracts::{TradingInstrumentDayOff, TradingInstrumentNoSqlEntity};
+
+use crate::{
+    convert_csharp_int_day_to_rust_weekday, get_instrument_holidays, get_instrument_timezone,
+    validate_day_off_in_timezone, validate_holidays, AppContext,
+};
+
+const SECONDS_IN_WEEK: i64 = 7 * 86400;
+
+pub struct InstrumentTradingSchedule {
+    pub is_open: bool,
+    pub next_close: Option<DateTime<Utc>>,
+    pub next_open: Option<DateTime<Utc>>,
+    pub holidays: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+pub async fn get_instrument_trading_schedule(
+    app: &AppContext,
+    instrument: &TradingInstrumentNoSqlEntity,
+    current_date: DateTime<Utc>,
+) -> InstrumentTradingSchedule {
+    let timezone = get_instrument_timezone(app, instrument.get_id()).await;
+    let holidays = get_instrument_holidays(app, instrument.get_id(), &timezone).await;
+
+    let is_open = instrument
+        .days_off
+        .iter()
+        .all(|day_off| validate_day_off_in_timezone(day_off, &timezone, current_date).is_ok())
+        && validate_holidays(&holidays, current_date).is_ok();
+
+    let mut intervals = get_day_off_intervals(&instrument.days_off, &timezone, current_date);
+    intervals.extend(holidays.iter().cloned());
+    let intervals = merge_intervals(intervals);
+
+    let mut schedule = get_trading_schedule(is_open, &intervals, current_date);
+    schedule.holidays = holidays
+        .into_iter()
+        .filter(|(_, to)| *to >= current_date)
+        .collect();
+
+    schedule
+}
+
+pub fn get_trading_schedule(
+    is_open: bool,
+    intervals: &[(DateTime<Utc>, DateTime<Utc>)],
+    current_date: DateTime<Utc>,
+) -> InstrumentTradingSchedule {
+    let current_interval = intervals
+        .iter()
+        .position(|(from, to)| *from <= current_date && current_date <= *to);
+
+    if let Some(index) = current_interval {
+        let (_, to) = intervals[index];
+        let next_interval = intervals.get(index + 1);
+
+        return InstrumentTradingSchedule {
+            is_open,
+            next_close: next_interval.map(|(from, _)| *from),
+            next_open: Some(to),
+            holidays: vec![],
+        };
+    }
+
+    let next_interval = intervals.iter().find(|(from, _)| *from > current_date);
+
+    InstrumentTradingSchedule {
+        is_open,
+        next_close: next_interval.map(|(from, _)| *from),
+        next_open: next_interval.map(|(_, to)| *to),
+        holidays: vec![],
+    }
+}
+
+/// Expands weekly day-off windows into concrete, merged intervals around `current_date`:
+/// from the previous week up to two weeks ahead. Windows are defined in the instrument's
+/// local time, so each occurrence is resolved separately to follow DST shifts.
+pub fn get_day_off_intervals(
+    days_off: &[TradingInstrumentDayOff],
+    timezone: &Tz,
+    current_date: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let local_date = current_date.with_timezone(timezone);
+    let week_start = (local_date.date_naive()
+        - Duration::days(local_date.weekday().num_days_from_monday() as i64))
+    .and_hms_opt(0, 0, 0)
+    .unwrap();
+
+    let mut intervals = vec![];
+
+    for day_off in days_off {
+        let from_offset = get_week_offset(day_off.dow_from, &day_off.time_from);
+        let mut to_offset = get_week_offset(day_off.dow_to, &day_off.time_to);
+
+        if to_offset <= from_offset {
+            to_offset += SECONDS_IN_WEEK;
+        }
+
+        for week in -1..=2 {
+            let week_start = week_start + Duration::weeks(week);
+            intervals.push((
+                local_to_utc(timezone, week_start + Duration::seconds(from_offset)),
+                local_to_utc(timezone, week_start + Duration::seconds(to_offset)),
+            ));
+        }
+    }
+
+    merge_intervals(intervals)
+}
+
+fn local_to_utc(timezone: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    // A local time inside a spring-forward gap does not exist; the window starts once the
+    // clocks have moved forward.
+    let date = timezone
+        .from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            timezone
+                .from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .unwrap();
+
+    date.with_timezone(&Utc)
+}
+
+pub fn merge_intervals(
+    mut intervals: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    intervals.sort_by_key(|(from, _)| *from);
+
+    let mut result: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::with_capacity(intervals.len());
+
+    for (from, to) in intervals {
+        if let Some((_, last_to)) = result.last_mut() {
+            if from <= *last_to {
+                if to > *last_to {
+                    *last_to = to;
+                }
+                continue;
+            }
+        }
+
+        result.push((from, to));
+    }
+
+    result
+}
+
+fn get_week_offset(dow: i32, time: &str) -> i64 {
+    let weekday = convert_csharp_int_day_to_rust_weekday(dow);
+    let time: NaiveTime = time.parse().unwrap();
+
+    weekday.num_days_from_monday() as i64 * 86400 + time.num_seconds_from_midnight() as i64
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Tz;
+    use my_nosql_contracts::TradingInstrumentDayOff;
+
+    use super::{get_day_off_intervals, get_trading_schedule, merge_intervals};
+
+    fn weekend_day_off() -> TradingInstrumentDayOff {
+        TradingInstrumentDayOff {
+            dow_from: 5,
+            time_from: "21:00:00".to_string(),
+            dow_to: 0,
+            time_to: "21:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn schedule_when_market_is_open() {
+        let date = Utc.with_ymd_and_hms(2023, 11, 22, 12, 0, 0).unwrap();
+        let intervals = get_day_off_intervals(&[weekend_day_off()], &Tz::UTC, date);
+
+        let schedule = get_trading_schedule(true, &intervals, date);
+
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 11, 24, 21, 0, 0).unwrap()),
+            schedule.next_close
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 11, 26, 21, 0, 0).unwrap()),
+            schedule.next_open
+        );
+    }
+
+    #[test]
+    fn schedule_when_market_is_closed() {
+        let date = Utc.with_ymd_and_hms(2023, 11, 25, 10, 0, 0).unwrap();
+        let intervals = get_day_off_intervals(&[weekend_day_off()], &Tz::UTC, date);
+
+        let schedule = get_trading_schedule(false, &intervals, date);
+
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 11, 26, 21, 0, 0).unwrap()),
+            schedule.next_open
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 12, 1, 21, 0, 0).unwrap()),
+            schedule.next_close
+        );
+    }
+
+    #[test]
+    fn schedule_with_holiday_extending_weekend() {
+        let date = Utc.with_ymd_and_hms(2023, 12, 22, 12, 0, 0).unwrap();
+        let mut intervals = get_day_off_intervals(&[weekend_day_off()], &Tz::UTC, date);
+        intervals.push((
+            Utc.with_ymd_and_hms(2023, 12, 24, 21, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2023, 12, 25, 23, 59, 59).unwrap(),
+        ));
+        let intervals = merge_intervals(intervals);
+
+        let schedule = get_trading_schedule(true, &intervals, date);
+
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 12, 22, 21, 0, 0).unwrap()),
+            schedule.next_close
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 12, 25, 23, 59, 59).unwrap()),
+            schedule.next_open
+        );
+    }
+
+    #[test]
+    fn schedule_with_week_wrap_around() {
+        let day_off = TradingInstrumentDayOff {
+            dow_from: 5,
+            time_from: "21:00:00".to_string(),
+            dow_to: 2,
+            time_to: "14:30:00".to_string(),
+        };
+
+        let date = Utc.with_ymd_and_hms(2023, 11, 20, 22, 0, 0).unwrap();
+        let intervals = get_day_off_intervals(&[day_off], &Tz::UTC, date);
+
+        let schedule = get_trading_schedule(false, &intervals, date);
+
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 11, 21, 14, 30, 0).unwrap()),
+            schedule.next_open
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 11, 24, 21, 0, 0).unwrap()),
+            schedule.next_close
+        );
+    }
+
+    #[test]
+    fn schedule_with_week_wrap_around_across_dst_change() {
+        let day_off = TradingInstrumentDayOff {
+            dow_from: 5,
+            time_from: "17:00:00".to_string(),
+            dow_to: 2,
+            time_to: "14:30:00".to_string(),
+        };
+
+        let date = Utc.with_ymd_and_hms(2023, 11, 6, 12, 0, 0).unwrap();
+        let intervals = get_day_off_intervals(&[day_off], &chrono_tz::America::New_York, date);
+
+        let schedule = get_trading_schedule(false, &intervals, date);
+
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 11, 7, 19, 30, 0).unwrap()),
+            schedule.next_open
+        );
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2023, 11, 10, 22, 0, 0).unwrap()),
+            schedule.next_close
+        );
+    }
+}
@@ -2,13 +2,18 @@ use std::sync::Arc;
 
 use crate::{
     accounts_manager_grpc::AccountManagerGetClientAccountGrpcRequest,
-    position_manager_grpc::PositionManagerUpdateSlTpGrpcRequest,
+    position_manager_grpc::{
+        PositionManagerGetActivePositionGrpcRequest, PositionManagerUpdateSlTpGrpcRequest,
+    },
     trading_executor_grpc::{
         TradingExecutorActivePositionGrpcModel, TradingExecutorUpdateSlTpGrpcRequest,
     },
-    AppContext, TradingExecutorError,
+    validate_market_state, AppContext, DownstreamContext, DownstreamService, TradingExecutorError,
+    TradingOperationType,
+};
+use my_nosql_contracts::{
+    TradingGroupNoSqlEntity, TradingInstrumentNoSqlEntity, TradingProfileNoSqlEntity,
 };
-use my_nosql_contracts::{TradingGroupNoSqlEntity, TradingProfileNoSqlEntity};
 use service_sdk::my_telemetry;
 
 pub async fn update_sl_tp(
@@ -16,22 +21,76 @@ pub async fn update_sl_tp(
     request: TradingExecutorUpdateSlTpGrpcRequest,
     telemetry: &my_telemetry::MyTelemetryContext,
 ) -> Result<TradingExecutorActivePositionGrpcModel, TradingExecutorError> {
-    let Some(target_account) = app
-        .accounts_manager_grpc_client
-        .get_client_account(
-            AccountManagerGetClientAccountGrpcRequest {
-                trader_id: request.trader_id.clone(),
-                account_id: request.account_id.clone(),
-            },
-            telemetry,
+    let downstream = DownstreamContext::new(
+        &request.trader_id,
+        &request.account_id,
+        &request.process_id,
+        telemetry,
+    );
+
+    let Some(target_account) = downstream
+        .call(
+            DownstreamService::AccountsManager,
+            "get_client_account",
+            app.accounts_manager_grpc_client.get_client_account(
+                AccountManagerGetClientAccountGrpcRequest {
+                    trader_id: request.trader_id.clone(),
+                    account_id: request.account_id.clone(),
+                },
+                telemetry,
+            ),
         )
-        .await
-        .unwrap()
+        .await?
         .account
     else {
         return Err(TradingExecutorError::AccountNotFound);
     };
 
+    let checks = app
+        .market_state_policy
+        .get_checks(TradingOperationType::ModifySlTp);
+
+    if checks.day_off || checks.liquidity {
+        let Some(target_position) = downstream
+            .call(
+                DownstreamService::PositionManager,
+                "get_active_position",
+                app.position_manager_grpc_client.get_active_position(
+                    PositionManagerGetActivePositionGrpcRequest {
+                        trader_id: request.trader_id.clone(),
+                        account_id: request.account_id.clone(),
+                        position_id: request.position_id.clone(),
+                    },
+                    telemetry,
+                ),
+            )
+            .await?
+            .position
+        else {
+            return Err(TradingExecutorError::PositionNotFound);
+        };
+
+        let Some(target_instrument) = app
+            .trading_instruments_reader
+            .get_entity(
+                TradingInstrumentNoSqlEntity::generate_partition_key(),
+                &target_position.asset_pair,
+            )
+            .await
+        else {
+            return Err(TradingExecutorError::InstrumentNotFound);
+        };
+
+        validate_market_state(
+            app,
+            &downstream,
+            TradingOperationType::ModifySlTp,
+            &target_instrument,
+            &target_account.currency,
+        )
+        .await?;
+    }
+
     let Some(target_trading_group) = app
         .trading_groups_reader
         .get_entity(
@@ -56,25 +115,30 @@ pub async fn update_sl_tp(
 
     let pm_request = PositionManagerUpdateSlTpGrpcRequest {
         position_id: request.position_id,
-        account_id: request.account_id,
-        trader_id: request.trader_id,
+        account_id: request.account_id.clone(),
+        trader_id: request.trader_id.clone(),
         tp_in_profit: request.tp_in_profit,
         tp_in_asset_price: request.tp_in_asset_price,
         sl_in_asset_price: request.sl_in_asset_price,
         sl_in_profit: request.sl_in_profit,
-        process_id: request.process_id,
+        process_id: request.process_id.clone(),
     };
 
-    let update_result = app
-        .position_manager_grpc_client
-        .update_sl_tp(pm_request, &my_telemetry::MyTelemetryContext::new())
-        .await;
-
-    let update_result = update_result.unwrap();
+    let update_result = downstream
+        .call(
+            DownstreamService::PositionManager,
+            "update_sl_tp",
+            app.position_manager_grpc_client
+                .update_sl_tp(pm_request, &my_telemetry::MyTelemetryContext::new()),
+        )
+        .await?;
 
     if let Some(position) = update_result.position {
         return Ok(position.into());
     }
 
-    return Err(TradingExecutorError::from(update_result.status));
+    return Err(TradingExecutorError::from_position_manager_code(
+        update_result.status,
+        &downstream,
+    ));
 }
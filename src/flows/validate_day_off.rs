@@ -1,22 +1,75 @@
 use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
 use my_nosql_contracts::{TradingInstrumentDayOff, TradingInstrumentNoSqlEntity};
 
-use crate::TradingExecutorError;
+use crate::{
+    get_instrument_holidays, validate_holidays, AppContext, InstrumentTradingSettingsNoSqlEntity,
+    TradingExecutorError,
+};
 
-pub fn validate_instrument_day_off(
+pub async fn validate_instrument_day_off(
+    app: &AppContext,
     instrument: &TradingInstrumentNoSqlEntity,
 ) -> Result<(), TradingExecutorError> {
+    let current_date = Utc::now();
+    let timezone = get_instrument_timezone(app, instrument.get_id()).await;
+
     for day_off in &instrument.days_off {
-        validate_day_off(&day_off, Utc::now())?;
+        validate_day_off_in_timezone(&day_off, &timezone, current_date)?;
     }
 
+    let holidays = get_instrument_holidays(app, instrument.get_id(), &timezone).await;
+    validate_holidays(&holidays, current_date)?;
+
     return Ok(());
 }
 
+pub async fn get_instrument_timezone(app: &AppContext, instrument_id: &str) -> Tz {
+    app.instrument_trading_settings_reader
+        .get_entity(
+            InstrumentTradingSettingsNoSqlEntity::generate_partition_key(),
+            instrument_id,
+        )
+        .await
+        .map(|x| get_settings_timezone(app, &x))
+        .unwrap_or(Tz::UTC)
+}
+
+/// An invalid timezone falls back to UTC and is logged once per instrument.
+pub fn get_settings_timezone(
+    app: &AppContext,
+    settings: &InstrumentTradingSettingsNoSqlEntity,
+) -> Tz {
+    match settings.get_timezone() {
+        Ok(timezone) => timezone,
+        Err(err) => {
+            app.invalid_settings_log.report(
+                "get_settings_timezone",
+                format!("timezone/{}", settings.row_key),
+                format!(
+                    "Invalid timezone {:?}, UTC is used: {}",
+                    settings.timezone, err
+                ),
+            );
+
+            Tz::UTC
+        }
+    }
+}
+
 pub fn validate_day_off(
     instrument: &TradingInstrumentDayOff,
     current_date: DateTime<Utc>,
 ) -> Result<(), TradingExecutorError> {
+    validate_day_off_in_timezone(instrument, &Tz::UTC, current_date)
+}
+
+pub fn validate_day_off_in_timezone(
+    instrument: &TradingInstrumentDayOff,
+    timezone: &Tz,
+    current_date: DateTime<Utc>,
+) -> Result<(), TradingExecutorError> {
+    let current_date = current_date.with_timezone(timezone);
     let current_weekday = current_date.weekday();
     let current_time = current_date.time();
 
@@ -54,7 +107,7 @@ fn as_int(weekday: Weekday, time: NaiveTime) -> u32 {
     return weekday as u32 * 86400 + time.hour() * 3600 + time.minute() * 60 + time.second();
 }
 
-fn convert_csharp_int_day_to_rust_weekday(src: i32) -> Weekday {
+pub fn convert_csharp_int_day_to_rust_weekday(src: i32) -> Weekday {
     if src == 0 {
         return Weekday::Sun;
     }
@@ -68,7 +121,7 @@ mod test {
     use chrono::{TimeZone, Utc};
     use my_nosql_contracts::TradingInstrumentDayOff;
 
-    use crate::validate_day_off;
+    use crate::{validate_day_off, validate_day_off_in_timezone};
 
     #[test]
     fn check_day_off_f_s_t_day_off() {
@@ -149,4 +202,72 @@ mod test {
 
         assert_eq!(false, validate_result.is_err());
     }
+
+    #[test]
+    fn check_day_off_tz_f_s_t_day_off_summer_time() {
+        let day_off = TradingInstrumentDayOff {
+            dow_from: 5,
+            time_from: "17:00:00".to_string(),
+            dow_to: 0,
+            time_to: "17:00:00".to_string(),
+        };
+
+        let date = Utc.with_ymd_and_hms(2023, 7, 14, 21, 30, 0).unwrap();
+
+        let validate_result =
+            validate_day_off_in_timezone(&day_off, &chrono_tz::America::New_York, date);
+
+        assert_eq!(true, validate_result.is_err());
+    }
+
+    #[test]
+    fn check_day_off_tz_f_s_t_day_on_winter_time() {
+        let day_off = TradingInstrumentDayOff {
+            dow_from: 5,
+            time_from: "17:00:00".to_string(),
+            dow_to: 0,
+            time_to: "17:00:00".to_string(),
+        };
+
+        let date = Utc.with_ymd_and_hms(2023, 11, 24, 21, 30, 0).unwrap();
+
+        let validate_result =
+            validate_day_off_in_timezone(&day_off, &chrono_tz::America::New_York, date);
+
+        assert_eq!(false, validate_result.is_err());
+    }
+
+    #[test]
+    fn check_day_off_tz_f_b_t_day_off_across_dst_change() {
+        let day_off = TradingInstrumentDayOff {
+            dow_from: 5,
+            time_from: "17:00:00".to_string(),
+            dow_to: 2,
+            time_to: "14:30:00".to_string(),
+        };
+
+        let date = Utc.with_ymd_and_hms(2023, 11, 7, 19, 20, 0).unwrap();
+
+        let validate_result =
+            validate_day_off_in_timezone(&day_off, &chrono_tz::America::New_York, date);
+
+        assert_eq!(true, validate_result.is_err());
+    }
+
+    #[test]
+    fn check_day_off_tz_f_b_t_day_on_across_dst_change() {
+        let day_off = TradingInstrumentDayOff {
+            dow_from: 5,
+            time_from: "17:00:00".to_string(),
+            dow_to: 2,
+            time_to: "14:30:00".to_string(),
+        };
+
+        let date = Utc.with_ymd_and_hms(2023, 11, 7, 19, 31, 0).unwrap();
+
+        let validate_result =
+            validate_day_off_in_timezone(&day_off, &chrono_tz::America::New_York, date);
+
+        assert_eq!(false, validate_result.is_err());
+    }
 }
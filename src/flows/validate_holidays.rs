@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::{AppContext, InstrumentHolidayNoSqlEntity, TradingExecutorError};
+
+pub async fn get_instrument_holidays(
+    app: &AppContext,
+    instrument_id: &str,
+    timezone: &Tz,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut result = vec![];
+
+    for partition_key in [
+        instrument_id,
+        InstrumentHolidayNoSqlEntity::ALL_INSTRUMENTS_PARTITION_KEY,
+    ] {
+        let Some(holidays) = app
+            .instrument_holidays_reader
+            .get_by_partition_key_as_vec(partition_key)
+            .await
+        else {
+            continue;
+        };
+
+        for holiday in holidays {
+            match holiday.get_interval(timezone) {
+                Some(interval) => result.push(interval),
+                None => app.invalid_settings_log.report(
+                    "get_instrument_holidays",
+                    format!("holiday/{}/{}", holiday.partition_key, holiday.row_key),
+                    format!(
+                        "Invalid holiday {} - {}, it is skipped",
+                        holiday.date_from, holiday.date_to
+                    ),
+                ),
+            }
+        }
+    }
+
+    return result;
+}
+
+pub fn validate_holidays(
+    holidays: &[(DateTime<Utc>, DateTime<Utc>)],
+    current_date: DateTime<Utc>,
+) -> Result<(), TradingExecutorError> {
+    let is_holiday = holidays
+        .iter()
+        .any(|(from, to)| *from <= current_date && current_date <= *to);
+
+    if is_holiday {
+        return Err(TradingExecutorError::DayOff);
+    }
+
+    return Ok(());
+}
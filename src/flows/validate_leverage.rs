@@ -0,0 +1,215 @@
+use crate::{
+    get_tier_max_leverage,
+    position_manager_grpc::{
+        PositionManagerGetActivePositionsGrpcRequest, PositionManagerGetPendingPositionsGrpcRequest,
+    },
+    trading_executor_grpc::{
+        TradingExecutorOpenPendingGrpcRequest, TradingExecutorOpenPositionGrpcRequest,
+    },
+    AccountLeverageNoSqlEntity, AppContext, DownstreamContext, DownstreamService,
+    TradingExecutorError,
+};
+
+const LEVERAGE_EPSILON: f64 = 1e-9;
+
+impl TradingExecutorOpenPositionGrpcRequest {
+    pub fn get_leverage(&self) -> f64 {
+        get_request_leverage(self.leverage, self.leverage_value)
+    }
+}
+
+impl TradingExecutorOpenPendingGrpcRequest {
+    pub fn get_leverage(&self) -> f64 {
+        get_request_leverage(self.leverage, self.leverage_value)
+    }
+}
+
+/// Clients that predate `LeverageValue` send the integer `Leverage` only.
+fn get_request_leverage(leverage: i32, leverage_value: Option<f64>) -> f64 {
+    leverage_value.unwrap_or(leverage as f64)
+}
+
+/// Account overrides take precedence over the trading profile list. An override for the
+/// instrument wins over the account-wide one. The executor instrument settings list replaces the
+/// integer list of the trading profile when configured.
+pub async fn get_allowed_leverages(
+    app: &AppContext,
+    account_id: &str,
+    trading_profile_id: &str,
+    instrument_id: &str,
+    profile_leverages: &[i32],
+) -> Vec<f64> {
+    let partition_key = AccountLeverageNoSqlEntity::generate_partition_key(account_id);
+
+    if let Some(entity) = app
+        .account_leverages_reader
+        .get_entity(partition_key, instrument_id)
+        .await
+    {
+        return entity.leverages.clone();
+    }
+
+    if let Some(entity) = app
+        .account_leverages_reader
+        .get_entity(
+            partition_key,
+            AccountLeverageNoSqlEntity::ALL_INSTRUMENTS_ROW_KEY,
+        )
+        .await
+    {
+        return entity.leverages.clone();
+    }
+
+    if let Some(leverages) = app
+        .trading_profile_instrument_settings_reader
+        .get_entity(trading_profile_id, instrument_id)
+        .await
+        .and_then(|x| x.leverages.clone())
+    {
+        return leverages;
+    }
+
+    profile_leverages.iter().map(|x| *x as f64).collect()
+}
+
+pub fn validate_leverage(
+    allowed_leverages: &[f64],
+    leverage: f64,
+) -> Result<(), TradingExecutorError> {
+    let is_allowed = leverage.is_finite()
+        && leverage > 0.0
+        && allowed_leverages
+            .iter()
+            .any(|x| (x - leverage).abs() < LEVERAGE_EPSILON);
+
+    if is_allowed {
+        return Ok(());
+    }
+
+    return Err(TradingExecutorError::MultiplierIsNotFound {
+        leverage,
+        max_leverage: allowed_leverages.iter().copied().reduce(f64::max),
+    });
+}
+
+/// Checks the leverage against the exposure tiers of the trading profile instrument. Exposure is
+/// the notional of the account's active and pending positions on the instrument plus the new
+/// order.
+pub async fn validate_leverage_tier(
+    app: &AppContext,
+    downstream: &DownstreamContext<'_>,
+    trading_profile_id: &str,
+    instrument_id: &str,
+    leverage: f64,
+    invest_amount: f64,
+) -> Result<(), TradingExecutorError> {
+    let Some(tiers) = app
+        .trading_profile_instrument_settings_reader
+        .get_entity(trading_profile_id, instrument_id)
+        .await
+        .and_then(|x| x.leverage_tiers.clone())
+    else {
+        return Ok(());
+    };
+
+    if tiers.is_empty() {
+        return Ok(());
+    }
+
+    let (active_positions, pending_positions) = tokio::join!(
+        downstream.call(
+            DownstreamService::PositionManager,
+            "get_account_active_positions",
+            app.position_manager_grpc_client
+                .get_account_active_positions(
+                    PositionManagerGetActivePositionsGrpcRequest {
+                        trader_id: downstream.trader_id.to_string(),
+                        account_id: downstream.account_id.to_string(),
+                    },
+                    downstream.telemetry_context,
+                ),
+        ),
+        downstream.call(
+            DownstreamService::PositionManager,
+            "get_account_pending_positions",
+            app.position_manager_grpc_client
+                .get_account_pending_positions(
+                    PositionManagerGetPendingPositionsGrpcRequest {
+                        trader_id: downstream.trader_id.to_string(),
+                        account_id: downstream.account_id.to_string(),
+                    },
+                    downstream.telemetry_context,
+                ),
+        )
+    );
+
+    let active_positions = active_positions?.unwrap_or_default();
+    let pending_positions = pending_positions?.unwrap_or_default();
+
+    let active_exposure: f64 = active_positions
+        .iter()
+        .filter(|x| x.asset_pair == instrument_id)
+        .map(|x| x.invest_amount * x.leverage)
+        .sum();
+
+    let pending_exposure: f64 = pending_positions
+        .iter()
+        .filter(|x| x.asset_pair == instrument_id)
+        .map(|x| x.invest_amount * x.leverage)
+        .sum();
+
+    let exposure = active_exposure + pending_exposure + invest_amount * leverage;
+
+    let Some(max_leverage) = get_tier_max_leverage(&tiers, exposure) else {
+        return Err(TradingExecutorError::MultiplierIsNotFound {
+            leverage,
+            max_leverage: None,
+        });
+    };
+
+    if leverage > max_leverage + LEVERAGE_EPSILON {
+        return Err(TradingExecutorError::MultiplierIsNotFound {
+            leverage,
+            max_leverage: Some(max_leverage),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::TradingExecutorError;
+
+    use super::{get_request_leverage, validate_leverage};
+
+    #[test]
+    fn reject_leverage_outside_profile_list() {
+        let result = validate_leverage(&[10.0, 50.0, 100.0], 200.0);
+
+        match result {
+            Err(TradingExecutorError::MultiplierIsNotFound {
+                leverage,
+                max_leverage,
+            }) => {
+                assert_eq!(200.0, leverage);
+                assert_eq!(Some(100.0), max_leverage);
+            }
+            _ => panic!("Leverage must be rejected"),
+        }
+
+        assert_eq!(true, validate_leverage(&[10.0, 50.0, 100.0], 50.0).is_ok());
+    }
+
+    #[test]
+    fn fractional_leverage() {
+        assert_eq!(true, validate_leverage(&[1.5, 2.5, 10.0], 2.5).is_ok());
+        assert_eq!(true, validate_leverage(&[1.5, 2.5, 10.0], 2.0).is_err());
+    }
+
+    #[test]
+    fn leverage_value_takes_precedence() {
+        assert_eq!(2.5, get_request_leverage(2, Some(2.5)));
+        assert_eq!(50.0, get_request_leverage(50, None));
+    }
+}
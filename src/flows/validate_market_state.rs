@@ -0,0 +1,87 @@
+use my_nosql_contracts::TradingInstrumentNoSqlEntity;
+
+use crate::{
+    validate_instrument_day_off, validate_instrument_pre_close, validate_timeout, AppContext,
+    DownstreamContext, TradingExecutorError, TradingOperationType,
+};
+
+pub async fn validate_market_state(
+    app: &AppContext,
+    downstream: &DownstreamContext<'_>,
+    operation: TradingOperationType,
+    instrument: &TradingInstrumentNoSqlEntity,
+    collateral: &str,
+) -> Result<(), TradingExecutorError> {
+    validate_market_hours(app, operation, instrument).await?;
+    validate_market_liquidity(app, downstream, operation, instrument, collateral).await?;
+
+    return Ok(());
+}
+
+/// Day off and pre-close checks. They don't depend on the account, so flows run them before
+/// any downstream call.
+pub async fn validate_market_hours(
+    app: &AppContext,
+    operation: TradingOperationType,
+    instrument: &TradingInstrumentNoSqlEntity,
+) -> Result<(), TradingExecutorError> {
+    let checks = app.market_state_policy.get_checks(operation);
+
+    if checks.day_off {
+        validate_instrument_day_off(app, instrument).await?;
+
+        if operation.adds_exposure() {
+            validate_instrument_pre_close(app, instrument).await?;
+        }
+    }
+
+    return Ok(());
+}
+
+/// Price freshness check. Needs the account collateral currency to resolve the cross rates.
+/// Stale price rejects are trade logged with the session that picked the timeout.
+pub async fn validate_market_liquidity(
+    app: &AppContext,
+    downstream: &DownstreamContext<'_>,
+    operation: TradingOperationType,
+    instrument: &TradingInstrumentNoSqlEntity,
+    collateral: &str,
+) -> Result<(), TradingExecutorError> {
+    let checks = app.market_state_policy.get_checks(operation);
+
+    if checks.liquidity {
+        let result = validate_timeout(
+            app,
+            instrument,
+            &instrument.base,
+            &instrument.quote,
+            collateral,
+        )
+        .await;
+
+        if let Err(TradingExecutorError::PriceIsStale {
+            age_seconds,
+            timeout_seconds,
+            session,
+        }) = &result
+        {
+            trade_log::trade_log!(
+                downstream.trader_id,
+                downstream.account_id,
+                downstream.process_id,
+                "n/a",
+                "Price is stale.",
+                downstream.telemetry_context.clone(),
+                "instrument" = &instrument.get_id().to_string(),
+                "session" = &session.as_str().to_string(),
+                "age_seconds" = &age_seconds.to_string(),
+                "timeout_seconds" = &timeout_seconds.to_string(),
+                "trace_id" = &downstream.telemetry_context.as_string()
+            );
+        }
+
+        result?;
+    }
+
+    return Ok(());
+}
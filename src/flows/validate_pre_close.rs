@@ -0,0 +1,93 @@
+use chrono::{DateTime, Duration, Utc};
+use my_nosql_contracts::TradingInstrumentNoSqlEntity;
+
+use crate::{
+    get_day_off_intervals, get_instrument_holidays, get_settings_timezone, merge_intervals,
+    AppContext, InstrumentTradingSettingsNoSqlEntity, TradingExecutorError,
+};
+
+pub async fn validate_instrument_pre_close(
+    app: &AppContext,
+    instrument: &TradingInstrumentNoSqlEntity,
+) -> Result<(), TradingExecutorError> {
+    let Some(settings) = app
+        .instrument_trading_settings_reader
+        .get_entity(
+            InstrumentTradingSettingsNoSqlEntity::generate_partition_key(),
+            instrument.get_id(),
+        )
+        .await
+    else {
+        return Ok(());
+    };
+
+    let Some(pre_close_minutes) = settings.pre_close_minutes else {
+        return Ok(());
+    };
+
+    let current_date = Utc::now();
+    let timezone = get_settings_timezone(app, &settings);
+    let mut intervals = get_day_off_intervals(&instrument.days_off, &timezone, current_date);
+    intervals.extend(get_instrument_holidays(app, instrument.get_id(), &timezone).await);
+    let intervals = merge_intervals(intervals);
+
+    validate_pre_close(
+        &intervals,
+        Duration::minutes(pre_close_minutes as i64),
+        current_date,
+    )
+}
+
+pub fn validate_pre_close(
+    day_off_intervals: &[(DateTime<Utc>, DateTime<Utc>)],
+    pre_close_window: Duration,
+    current_date: DateTime<Utc>,
+) -> Result<(), TradingExecutorError> {
+    let next_close = day_off_intervals
+        .iter()
+        .find(|(from, _)| *from > current_date && *from - current_date <= pre_close_window);
+
+    if let Some((from, _)) = next_close {
+        return Err(TradingExecutorError::MarketClosingSoon {
+            closes_in_seconds: (*from - current_date).num_seconds(),
+            window_seconds: pre_close_window.num_seconds(),
+        });
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::validate_pre_close;
+
+    #[test]
+    fn check_pre_close_inside_window() {
+        let intervals = vec![(
+            Utc.with_ymd_and_hms(2023, 11, 24, 21, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2023, 11, 26, 21, 0, 0).unwrap(),
+        )];
+
+        let date = Utc.with_ymd_and_hms(2023, 11, 24, 20, 50, 0).unwrap();
+
+        let validate_result = validate_pre_close(&intervals, Duration::minutes(15), date);
+
+        assert_eq!(true, validate_result.is_err());
+    }
+
+    #[test]
+    fn check_pre_close_outside_window() {
+        let intervals = vec![(
+            Utc.with_ymd_and_hms(2023, 11, 24, 21, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2023, 11, 26, 21, 0, 0).unwrap(),
+        )];
+
+        let date = Utc.with_ymd_and_hms(2023, 11, 24, 20, 40, 0).unwrap();
+
+        let validate_result = validate_pre_close(&intervals, Duration::minutes(15), date);
+
+        assert_eq!(false, validate_result.is_err());
+    }
+}
@@ -1,8 +1,12 @@
-use chrono::Utc;
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
 use my_nosql_contracts::TradingInstrumentNoSqlEntity;
 use service_sdk::rust_extensions::date_time::DateTimeAsMicroseconds;
 
-use crate::{AppContext, TradingExecutorError};
+use crate::{
+    get_instrument_holidays, get_settings_timezone, resolve_cross_rate, AppContext,
+    InstrumentTradingSettingsNoSqlEntity, PriceTimeoutSession, TradingExecutorError,
+};
 
 pub async fn validate_timeout(
     app: &AppContext,
@@ -11,106 +15,179 @@ pub async fn validate_timeout(
     quote: &str,
     collateral: &str,
 ) -> Result<(), TradingExecutorError> {
-    let Some(bid_asks) = app
-        .bid_ask_snapshot_ns_reader
-        .get_table_snapshot_as_vec()
-        .await
-    else {
-        return Err(TradingExecutorError::NoLiquidity);
-    };
-
-    //check base-collateral
-    if base != collateral {
-        let Some(base_collateral) = bid_asks.iter().find(|x| {
-            (x.base == base && x.quote == collateral) || x.base == collateral && x.quote == base
-        }) else {
-            return Err(TradingExecutorError::NoLiquidity);
-        };
-
-        let Some(instrument) = app
-            .trading_instruments_reader
-            .get_entity(
-                TradingInstrumentNoSqlEntity::generate_partition_key(),
-                &base_collateral.row_key,
-            )
-            .await
-        else {
-            return Err(TradingExecutorError::InstrumentNotFound);
-        };
-
-        validate_instrument_timeout(&instrument, base_collateral.unix_timestamp_with_milis)?;
-    }
+    //check base-collateral and quote-collateral, every hop of the conversion must be fresh
+    resolve_cross_rate(app, base, collateral).await?;
+    resolve_cross_rate(app, quote, collateral).await?;
 
-    if quote != collateral {
-        let Some(quote_collateral) = bid_asks.iter().find(|x| {
-            (x.base == quote && x.quote == collateral) || x.base == collateral && x.quote == quote
-        }) else {
-            return Err(TradingExecutorError::NoLiquidity);
-        };
-
-        let Some(instrument) = app
-            .trading_instruments_reader
-            .get_entity(
-                TradingInstrumentNoSqlEntity::generate_partition_key(),
-                &quote_collateral.row_key,
-            )
-            .await
-        else {
-            return Err(TradingExecutorError::InstrumentNotFound);
-        };
-
-        validate_instrument_timeout(&instrument, quote_collateral.unix_timestamp_with_milis)?;
-    }
-
-    let Some(asset_bidask) = bid_asks
-        .iter()
-        .find(|x| x.row_key == asset_instrument.get_id())
-    else {
+    let Some(asset_bidask) = app.bid_ask_cache.get_by_id(asset_instrument.get_id()).await else {
         return Err(TradingExecutorError::NoLiquidity);
     };
 
-    let Some(instrument) = app
-        .trading_instruments_reader
-        .get_entity(
-            TradingInstrumentNoSqlEntity::generate_partition_key(),
-            &asset_bidask.row_key,
-        )
-        .await
-    else {
-        return Err(TradingExecutorError::InstrumentNotFound);
-    };
-
-    validate_instrument_timeout(instrument.as_ref(), asset_bidask.unix_timestamp_with_milis)?;
+    validate_instrument_timeout(app, asset_instrument, asset_bidask.unix_timestamp_with_milis)
+        .await?;
     return Ok(());
 }
 
-pub fn validate_instrument_timeout(
+pub async fn validate_instrument_timeout(
+    app: &AppContext,
     instrument: &TradingInstrumentNoSqlEntity,
     last_bidask_date: u64,
 ) -> Result<(), TradingExecutorError> {
     let current_date = Utc::now();
-    let ns_date: DateTimeAsMicroseconds = last_bidask_date.into();
-    let ns_date = ns_date.to_chrono_utc();
 
-    if let Some(day_t) = instrument.day_timeout {
-        let timeout = chrono::Duration::seconds(day_t as i64);
-        let diff = current_date - ns_date;
-        if diff > timeout {
-            return Err(TradingExecutorError::NoLiquidity);
+    let settings = app
+        .instrument_trading_settings_reader
+        .get_entity(
+            InstrumentTradingSettingsNoSqlEntity::generate_partition_key(),
+            instrument.get_id(),
+        )
+        .await;
+
+    let day_session = settings
+        .as_ref()
+        .and_then(|x| x.get_day_session().map(|day_session| (x, day_session)));
+
+    let session = if let Some((settings, day_session)) = day_session {
+        let timezone = get_settings_timezone(app, settings);
+        let holidays = get_instrument_holidays(app, instrument.get_id(), &timezone).await;
+        get_price_timeout_session(day_session, &timezone, &holidays, current_date)
+    } else {
+        match instrument.day_timeout {
+            Some(_) => PriceTimeoutSession::Day,
+            None => PriceTimeoutSession::Night,
         }
+    };
 
-        return Ok(());
-    }
-
-    if let Some(day_n) = instrument.night_timeout {
-        let timeout = chrono::Duration::seconds(day_n as i64);
-        let diff = current_date - ns_date;
-        if diff > timeout {
-            return Err(TradingExecutorError::NoLiquidity);
+    let timeout = match session {
+        PriceTimeoutSession::Day => instrument.day_timeout.or(instrument.night_timeout),
+        PriceTimeoutSession::Night | PriceTimeoutSession::Holiday => {
+            instrument.night_timeout.or(instrument.day_timeout)
         }
+    };
 
+    let Some(timeout) = timeout else {
         return Ok(());
+    };
+
+    let ns_date: DateTimeAsMicroseconds = last_bidask_date.into();
+    let ns_date = ns_date.to_chrono_utc();
+
+    let timeout = chrono::Duration::seconds(timeout as i64);
+    let diff = current_date - ns_date;
+    if diff > timeout {
+        return Err(TradingExecutorError::PriceIsStale {
+            age_seconds: diff.num_seconds(),
+            timeout_seconds: timeout.num_seconds(),
+            session,
+        });
     }
 
     return Ok(());
 }
+
+/// Day session hours are local to the instrument timezone and may wrap midnight.
+/// Any local date touched by a holiday closure is a holiday session.
+pub fn get_price_timeout_session(
+    day_session: (NaiveTime, NaiveTime),
+    timezone: &Tz,
+    holidays: &[(DateTime<Utc>, DateTime<Utc>)],
+    current_date: DateTime<Utc>,
+) -> PriceTimeoutSession {
+    let local_date = current_date.with_timezone(timezone);
+
+    let is_holiday_date = holidays.iter().any(|(from, to)| {
+        from.with_timezone(timezone).date_naive() <= local_date.date_naive()
+            && local_date.date_naive() <= to.with_timezone(timezone).date_naive()
+    });
+
+    if is_holiday_date {
+        return PriceTimeoutSession::Holiday;
+    }
+
+    let (from, to) = day_session;
+    let time = local_date.time();
+
+    let is_day_session = match from <= to {
+        true => from <= time && time < to,
+        false => time >= from || time < to,
+    };
+
+    match is_day_session {
+        true => PriceTimeoutSession::Day,
+        false => PriceTimeoutSession::Night,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{NaiveTime, TimeZone, Utc};
+    use chrono_tz::Tz;
+
+    use super::{get_price_timeout_session, PriceTimeoutSession};
+
+    fn day_session() -> (NaiveTime, NaiveTime) {
+        (
+            NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+            NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+        )
+    }
+
+    #[test]
+    fn check_day_session() {
+        let date = Utc.with_ymd_and_hms(2023, 11, 22, 15, 0, 0).unwrap();
+
+        let session = get_price_timeout_session(
+            day_session(),
+            &chrono_tz::America::New_York,
+            &[],
+            date,
+        );
+
+        assert_eq!(PriceTimeoutSession::Day, session);
+    }
+
+    #[test]
+    fn check_night_session() {
+        let date = Utc.with_ymd_and_hms(2023, 11, 22, 22, 0, 0).unwrap();
+
+        let session = get_price_timeout_session(
+            day_session(),
+            &chrono_tz::America::New_York,
+            &[],
+            date,
+        );
+
+        assert_eq!(PriceTimeoutSession::Night, session);
+    }
+
+    #[test]
+    fn check_day_session_wrapping_midnight() {
+        let day_session = (
+            NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+        );
+        let date = Utc.with_ymd_and_hms(2023, 11, 22, 2, 0, 0).unwrap();
+
+        let session = get_price_timeout_session(day_session, &Tz::UTC, &[], date);
+
+        assert_eq!(PriceTimeoutSession::Day, session);
+    }
+
+    #[test]
+    fn check_holiday_date_is_holiday_session() {
+        let holidays = vec![(
+            Utc.with_ymd_and_hms(2023, 11, 24, 18, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2023, 11, 24, 23, 59, 59).unwrap(),
+        )];
+        let date = Utc.with_ymd_and_hms(2023, 11, 24, 15, 0, 0).unwrap();
+
+        let session = get_price_timeout_session(
+            day_session(),
+            &chrono_tz::America::New_York,
+            &holidays,
+            date,
+        );
+
+        assert_eq!(PriceTimeoutSession::Holiday, session);
+    }
+}
@@ -1,16 +1,17 @@
 use crate::{
-    a_book_bridge_grpc::ABookBridgePositionSide,
+    a_book_bridge_grpc::{ABookBridgePositionSide, OpenPositionGrpcResponseStatusCode},
+    accounts_manager_grpc::AccountsManagerOperationResult,
     position_manager_grpc::{
         PositionManagerActivePositionGrpcModel, PositionManagerBidAsk,
-        PositionManagerClosedPositionGrpcModel, PositionManagerPendingPositionGrpcModel,
-        PositionManagerSwapGrpcModel,
+        PositionManagerClosedPositionGrpcModel, PositionManagerOperationsCodes,
+        PositionManagerPendingPositionGrpcModel, PositionManagerSwapGrpcModel,
     },
     trading_executor_grpc::{
         TradingExecutorActivePositionGrpcModel, TradingExecutorBidAsk,
         TradingExecutorClosedPositionGrpcModel, TradingExecutorPendingPositionGrpcModel,
         TradingExecutorPositionSide, TradingExecutorSwapGrpcModel,
     },
-    TradingExecutorError,
+    DownstreamContext, DownstreamService, ExecutableBidAsk, TradingExecutorError,
 };
 
 impl Into<TradingExecutorPendingPositionGrpcModel> for PositionManagerPendingPositionGrpcModel {
@@ -126,13 +127,87 @@ impl Into<TradingExecutorBidAsk> for PositionManagerBidAsk {
     }
 }
 
-impl From<i32> for TradingExecutorError {
-    fn from(value: i32) -> Self {
+impl Into<PositionManagerBidAsk> for &ExecutableBidAsk {
+    fn into(self) -> PositionManagerBidAsk {
+        PositionManagerBidAsk {
+            asset_pair: self.asset_pair.clone(),
+            bid: self.bid,
+            ask: self.ask,
+            date_time_unix_timestamp_milis: self.date_time_unix_timestamp_milis,
+        }
+    }
+}
+
+impl From<PositionManagerOperationsCodes> for TradingExecutorError {
+    fn from(value: PositionManagerOperationsCodes) -> Self {
+        match value {
+            // Ok without a position in the response is a contract violation
+            PositionManagerOperationsCodes::Ok => TradingExecutorError::TechError,
+            PositionManagerOperationsCodes::NoLiquidity => TradingExecutorError::NoLiquidity,
+            PositionManagerOperationsCodes::PositionNotFound => {
+                TradingExecutorError::PositionNotFound
+            }
+            PositionManagerOperationsCodes::ToppingUpDisabledForPosition => {
+                TradingExecutorError::ToppingUpDisabled
+            }
+            PositionManagerOperationsCodes::MarginCallSettingsNotFound => {
+                TradingExecutorError::MarginCallSettingsNotFound
+            }
+        }
+    }
+}
+
+impl From<AccountsManagerOperationResult> for TradingExecutorError {
+    fn from(value: AccountsManagerOperationResult) -> Self {
         match value {
-            0 => panic!("Can't convert 0 to TradingExecutorError"),
-            1 => TradingExecutorError::NoLiquidity,
-            2 => TradingExecutorError::PositionNotFound,
-            _ => panic!("Invalud operation code from position manager"),
+            AccountsManagerOperationResult::Ok => TradingExecutorError::TechError,
+            AccountsManagerOperationResult::AccountNotFound
+            | AccountsManagerOperationResult::TraderNotFound => {
+                TradingExecutorError::AccountNotFound
+            }
+            AccountsManagerOperationResult::NotEnoughBalance => {
+                TradingExecutorError::NotEnoughBalance
+            }
+            AccountsManagerOperationResult::ProcessIdDuplicate => {
+                TradingExecutorError::ProcessIdDuplicate
+            }
+        }
+    }
+}
+
+impl From<OpenPositionGrpcResponseStatusCode> for TradingExecutorError {
+    fn from(value: OpenPositionGrpcResponseStatusCode) -> Self {
+        match value {
+            OpenPositionGrpcResponseStatusCode::Success => TradingExecutorError::TechError,
+            OpenPositionGrpcResponseStatusCode::LiquidityProviderNotFound
+            | OpenPositionGrpcResponseStatusCode::InstrumentNotFoundInLpMapping
+            | OpenPositionGrpcResponseStatusCode::TradingInstrumentNotFound
+            | OpenPositionGrpcResponseStatusCode::LpReject => TradingExecutorError::ABookReject,
+            OpenPositionGrpcResponseStatusCode::Timeout
+            | OpenPositionGrpcResponseStatusCode::Disconnect => TradingExecutorError::TechError,
+        }
+    }
+}
+
+impl TradingExecutorError {
+    pub fn from_position_manager_code(value: i32, downstream: &DownstreamContext) -> Self {
+        match PositionManagerOperationsCodes::try_from(value) {
+            Ok(code) => code.into(),
+            Err(_) => downstream.unknown_code(DownstreamService::PositionManager, value),
+        }
+    }
+
+    pub fn from_accounts_manager_code(value: i32, downstream: &DownstreamContext) -> Self {
+        match AccountsManagerOperationResult::try_from(value) {
+            Ok(code) => code.into(),
+            Err(_) => downstream.unknown_code(DownstreamService::AccountsManager, value),
+        }
+    }
+
+    pub fn from_a_book_bridge_code(value: i32, downstream: &DownstreamContext) -> Self {
+        match OpenPositionGrpcResponseStatusCode::try_from(value) {
+            Ok(code) => code.into(),
+            Err(_) => downstream.unknown_code(DownstreamService::ABookBridge, value),
         }
     }
 }
@@ -145,3 +220,48 @@ impl Into<ABookBridgePositionSide> for TradingExecutorPositionSide {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use service_sdk::my_telemetry::MyTelemetryContext;
+
+    use crate::{DownstreamContext, TradingExecutorError};
+
+    #[test]
+    fn map_known_position_manager_codes() {
+        let telemetry_context = MyTelemetryContext::new();
+        let downstream = DownstreamContext::new("trader", "account", "process", &telemetry_context);
+
+        assert!(matches!(
+            TradingExecutorError::from_position_manager_code(3, &downstream),
+            TradingExecutorError::ToppingUpDisabled
+        ));
+        assert!(matches!(
+            TradingExecutorError::from_position_manager_code(4, &downstream),
+            TradingExecutorError::MarginCallSettingsNotFound
+        ));
+    }
+
+    #[test]
+    fn map_unknown_codes_to_tech_error() {
+        let telemetry_context = MyTelemetryContext::new();
+        let downstream = DownstreamContext::new("trader", "account", "process", &telemetry_context);
+
+        assert!(matches!(
+            TradingExecutorError::from_position_manager_code(0, &downstream),
+            TradingExecutorError::TechError
+        ));
+        assert!(matches!(
+            TradingExecutorError::from_position_manager_code(100, &downstream),
+            TradingExecutorError::TechError
+        ));
+        assert!(matches!(
+            TradingExecutorError::from_accounts_manager_code(-1, &downstream),
+            TradingExecutorError::TechError
+        ));
+        assert!(matches!(
+            TradingExecutorError::from_a_book_bridge_code(42, &downstream),
+            TradingExecutorError::TechError
+        ));
+    }
+}
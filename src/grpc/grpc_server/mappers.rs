@@ -1,15 +1,27 @@
-use crate::{TradingExecutorError, trading_executor_grpc::TradingExecutorOperationsCodes};
+use my_nosql_contracts::TradingInstrumentDayOff;
+
+use crate::{
+    trading_executor_grpc::{
+        TradingExecutorBidAsk, TradingExecutorCheckOpenPositionGrpcResponse,
+        TradingExecutorDayOffGrpcModel, TradingExecutorFailedCheckGrpcModel,
+        TradingExecutorOperationsCodes, TradingExecutorRejectReasonGrpcModel,
+    },
+    ExecutableBidAsk, OpenPositionCheck, RejectReason, TradingExecutorError,
+};
 
 impl Into<TradingExecutorOperationsCodes> for TradingExecutorError {
     fn into(self) -> TradingExecutorOperationsCodes {
         match self{
             TradingExecutorError::DayOff => TradingExecutorOperationsCodes::DayOff,
-            TradingExecutorError::OperationIsTooLow => TradingExecutorOperationsCodes::OperationIsTooLow,
-            TradingExecutorError::OperationIsTooHigh => TradingExecutorOperationsCodes::OperationIsTooHigh,
+            TradingExecutorError::OperationIsTooLow { .. } => TradingExecutorOperationsCodes::OperationIsTooLow,
+            TradingExecutorError::OperationIsTooHigh { .. } => TradingExecutorOperationsCodes::OperationIsTooHigh,
             TradingExecutorError::MinOperationsByInstrumentViolated => TradingExecutorOperationsCodes::MinOperationsByInstrumentViolated,
             TradingExecutorError::MaxOperationsByInstrumentViolated => TradingExecutorOperationsCodes::MaxOperationsByInstrumentViolated,
             TradingExecutorError::NotEnoughBalance => TradingExecutorOperationsCodes::NotEnoughBalance,
+            TradingExecutorError::NotEnoughFreeMargin { .. } => TradingExecutorOperationsCodes::NotEnoughBalance,
+            TradingExecutorError::MarginLevelTooLow { .. } => TradingExecutorOperationsCodes::NotEnoughBalance,
             TradingExecutorError::NoLiquidity => TradingExecutorOperationsCodes::NoLiquidity,
+            TradingExecutorError::PriceIsStale { .. } => TradingExecutorOperationsCodes::NoLiquidity,
             TradingExecutorError::PositionNotFound => TradingExecutorOperationsCodes::PositionNotFound,
             TradingExecutorError::TpIsTooClose => TradingExecutorOperationsCodes::TpIsTooClose,
             TradingExecutorError::SlIsTooClose => TradingExecutorOperationsCodes::SlIsTooClose,
@@ -18,13 +30,89 @@ impl Into<TradingExecutorOperationsCodes> for TradingExecutorError {
             TradingExecutorError::InstrumentIsNotTradable => TradingExecutorOperationsCodes::InstrumentIsNotTradable,
             TradingExecutorError::HitMaxAmountOfPendingOrders => TradingExecutorOperationsCodes::HitMaxAmountOfPendingOrders,
             TradingExecutorError::TechError => TradingExecutorOperationsCodes::TechError,
-            TradingExecutorError::MultiplierIsNotFound => TradingExecutorOperationsCodes::MultiplierIsNotFound,
+            TradingExecutorError::MultiplierIsNotFound { .. } => TradingExecutorOperationsCodes::MultiplierIsNotFound,
             TradingExecutorError::TradingDisabled => TradingExecutorOperationsCodes::TradingDisabled,
             TradingExecutorError::MaxPositionsAmount => TradingExecutorOperationsCodes::MaxPositionsAmount,
             TradingExecutorError::TradingGroupNotFound => TradingExecutorOperationsCodes::TradingGroupNotFound,
             TradingExecutorError::TradingProfileNotFound => TradingExecutorOperationsCodes::TradingProfileNotFound,
             TradingExecutorError::TradingProfileInstrumentNotFound => TradingExecutorOperationsCodes::TradingProfileInstrumentNotFound,
             TradingExecutorError::ABookReject => TradingExecutorOperationsCodes::ABookReject,
+            TradingExecutorError::MarketClosingSoon { .. } => TradingExecutorOperationsCodes::MarketClosingSoon,
+            TradingExecutorError::ProcessIdDuplicate => TradingExecutorOperationsCodes::ProcessIdDuplicate,
+            TradingExecutorError::ToppingUpDisabled => TradingExecutorOperationsCodes::ToppingUpDisabled,
+            TradingExecutorError::MarginCallSettingsNotFound => TradingExecutorOperationsCodes::MarginCallSettingsNotFound,
+            TradingExecutorError::Downstream(_) => TradingExecutorOperationsCodes::DownstreamUnavailable,
+            TradingExecutorError::InvalidCursor => TradingExecutorOperationsCodes::InvalidRequest,
+            TradingExecutorError::InvalidSizingValue { .. } => TradingExecutorOperationsCodes::InvalidRequest,
+            TradingExecutorError::ContractSizeNotConfigured => TradingExecutorOperationsCodes::InvalidRequest,
+            TradingExecutorError::StopLossRequired => TradingExecutorOperationsCodes::InvalidRequest,
+            TradingExecutorError::ProfitSortNotSupported => TradingExecutorOperationsCodes::InvalidRequest,
+        }
+    }
+}
+
+impl Into<TradingExecutorRejectReasonGrpcModel> for RejectReason {
+    fn into(self) -> TradingExecutorRejectReasonGrpcModel {
+        TradingExecutorRejectReasonGrpcModel {
+            rule_id: self.rule_id.to_string(),
+            value: self.value,
+            limit: self.limit,
+            localization_key: self.get_localization_key(),
+            message: self.message,
+        }
+    }
+}
+
+impl Into<TradingExecutorFailedCheckGrpcModel> for TradingExecutorError {
+    fn into(self) -> TradingExecutorFailedCheckGrpcModel {
+        let reason = Some(self.get_reject_reason().into());
+        let status: TradingExecutorOperationsCodes = self.into();
+
+        TradingExecutorFailedCheckGrpcModel {
+            status: status as i32,
+            reason,
+        }
+    }
+}
+
+impl Into<TradingExecutorCheckOpenPositionGrpcResponse> for OpenPositionCheck {
+    fn into(self) -> TradingExecutorCheckOpenPositionGrpcResponse {
+        let failed_checks: Vec<TradingExecutorFailedCheckGrpcModel> =
+            self.failed_checks.into_iter().map(|x| x.into()).collect();
+
+        TradingExecutorCheckOpenPositionGrpcResponse {
+            status: failed_checks
+                .first()
+                .map(|x| x.status)
+                .unwrap_or(TradingExecutorOperationsCodes::Ok as i32),
+            failed_checks,
+            required_margin: self.required_margin,
+            commission: self.commission,
+            indicative_price: self.indicative_price,
+            bid_ask: self.bid_ask.as_ref().map(|x| x.into()),
+            invest_amount: self.invest_amount,
+        }
+    }
+}
+
+impl Into<TradingExecutorDayOffGrpcModel> for &TradingInstrumentDayOff {
+    fn into(self) -> TradingExecutorDayOffGrpcModel {
+        TradingExecutorDayOffGrpcModel {
+            dow_from: self.dow_from,
+            time_from: self.time_from.clone(),
+            dow_to: self.dow_to,
+            time_to: self.time_to.clone(),
+        }
+    }
+}
+
+impl Into<TradingExecutorBidAsk> for &ExecutableBidAsk {
+    fn into(self) -> TradingExecutorBidAsk {
+        TradingExecutorBidAsk {
+            asset_pair: self.asset_pair.clone(),
+            bid: self.bid,
+            ask: self.ask,
+            date_time_unix_timestamp_milliseconds: self.date_time_unix_timestamp_milis,
         }
     }
-}
\ No newline at end of file
+}
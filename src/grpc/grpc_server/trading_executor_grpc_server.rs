@@ -1,23 +1,39 @@
 use crate::{
-    cancel_pending, close_position, open_limit, open_position,
+    cancel_all_pending, cancel_pending, check_open_position, close_position,
+    get_account_closed_positions, get_account_summary, get_instrument_trading_schedule,
+    get_quote, open_limit, open_position,
     position_manager_grpc::{
         PositionManagerGetActivePositionsGrpcRequest, PositionManagerGetPendingPositionsGrpcRequest,
     },
     trading_executor_grpc::{
         trading_executor_grpc_service_server::TradingExecutorGrpcService,
-        TradingExecutorActivePositionGrpcModel, TradingExecutorCancelPendingGrpcRequest,
-        TradingExecutorCancelPendingPositionGrpcResponse, TradingExecutorClosePositionGrpcRequest,
+        TradingExecutorActivePositionGrpcModel, TradingExecutorBidAsk,
+        TradingExecutorCancelAllPendingGrpcRequest,
+        TradingExecutorCancelAllPendingResultGrpcModel, TradingExecutorCancelPendingGrpcRequest,
+        TradingExecutorCancelPendingPositionGrpcResponse,
+        TradingExecutorCheckOpenPositionGrpcResponse, TradingExecutorClosePositionGrpcRequest,
         TradingExecutorClosePositionGrpcResponse,
+        TradingExecutorGetAccountClosedPositionsGrpcRequest,
+        TradingExecutorGetAccountClosedPositionsGrpcResponse,
         TradingExecutorGetAccountPendingPositionGrpcRequest,
-        TradingExecutorGetActivePositionsGrpcRequest, TradingExecutorOpenPendingGrpcRequest,
+        TradingExecutorGetAccountSummaryGrpcRequest, TradingExecutorGetAccountSummaryGrpcResponse,
+        TradingExecutorGetActivePositionsGrpcRequest,
+        TradingExecutorGetInstrumentTradingScheduleGrpcRequest, TradingExecutorGetQuoteGrpcRequest,
+        TradingExecutorGetQuoteGrpcResponse, TradingExecutorHolidayGrpcModel,
+        TradingExecutorInstrumentTradingScheduleGrpcResponse, TradingExecutorOpenPendingGrpcRequest,
         TradingExecutorOpenPendingPositionGrpcResponse, TradingExecutorOpenPositionGrpcRequest,
         TradingExecutorOpenPositionGrpcResponse, TradingExecutorOperationsCodes,
-        TradingExecutorPendingPositionGrpcModel, TradingExecutorUpdateSlTpGrpcRequest,
+        TradingExecutorPendingPositionGrpcModel, TradingExecutorPositionEventGrpcModel,
+        TradingExecutorSubscribeAccountPositionsGrpcRequest,
+        TradingExecutorSubscribeQuotesGrpcRequest,
+        TradingExecutorUpdateSlTpGrpcRequest,
         TradingExecutorUpdateSlTpGrpcResponse,
     },
-    update_sl_tp, GrpcService,
+    subscribe_account_positions, subscribe_quotes, update_sl_tp, DownstreamContext,
+    DownstreamService, GrpcService, PositionListQuery, TradingExecutorError,
 };
 use my_grpc_extensions::prelude::Stream;
+use my_nosql_contracts::TradingInstrumentNoSqlEntity;
 use my_grpc_extensions::server::with_telemetry;
 use service_sdk::my_grpc_extensions;
 use std::pin::Pin;
@@ -42,6 +58,33 @@ impl TradingExecutorGrpcService for GrpcService {
         >,
     >;
 
+    type CancelAllPendingPositionsStream = Pin<
+        Box<
+            dyn Stream<Item = Result<TradingExecutorCancelAllPendingResultGrpcModel, tonic::Status>>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    >;
+
+    type SubscribeQuotesStream = Pin<
+        Box<
+            dyn Stream<Item = Result<TradingExecutorBidAsk, tonic::Status>>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    >;
+
+    type SubscribeAccountPositionsStream = Pin<
+        Box<
+            dyn Stream<Item = Result<TradingExecutorPositionEventGrpcModel, tonic::Status>>
+                + Send
+                + Sync
+                + 'static,
+        >,
+    >;
+
     // #[with_telemetry]
     async fn open_position(
         &self,
@@ -53,15 +96,20 @@ impl TradingExecutorGrpcService for GrpcService {
         let open_position_result = open_position(&self.app, request, my_telemetry).await;
 
         let response = match open_position_result {
-            Ok(position) => TradingExecutorOpenPositionGrpcResponse {
+            Ok((position, commission)) => TradingExecutorOpenPositionGrpcResponse {
                 status: TradingExecutorOperationsCodes::Ok as i32,
+                reject_reason: None,
                 position: Some(position),
+                commission: Some(commission),
             },
             Err(error) => {
+                let reject_reason = Some(error.get_reject_reason().into());
                 let error: TradingExecutorOperationsCodes = error.into();
                 TradingExecutorOpenPositionGrpcResponse {
                     status: error.into(),
+                    reject_reason,
                     position: None,
+                    commission: None,
                 }
             }
         };
@@ -79,15 +127,20 @@ impl TradingExecutorGrpcService for GrpcService {
         let open_position_result = close_position(&self.app, request, my_telemetry).await;
 
         let response = match open_position_result {
-            Ok(position) => TradingExecutorClosePositionGrpcResponse {
+            Ok((position, commission)) => TradingExecutorClosePositionGrpcResponse {
                 status: 0,
+                reject_reason: None,
                 position: Some(position),
+                commission: Some(commission),
             },
             Err(error) => {
+                let reject_reason = Some(error.get_reject_reason().into());
                 let error: TradingExecutorOperationsCodes = error.into();
                 TradingExecutorClosePositionGrpcResponse {
                     status: error.into(),
+                    reject_reason,
                     position: None,
+                    commission: None,
                 }
             }
         };
@@ -108,12 +161,15 @@ impl TradingExecutorGrpcService for GrpcService {
         let response = match result {
             Ok(position) => TradingExecutorOpenPendingPositionGrpcResponse {
                 status: 0,
+                reject_reason: None,
                 position: Some(position),
             },
             Err(error) => {
+                let reject_reason = Some(error.get_reject_reason().into());
                 let error: TradingExecutorOperationsCodes = error.into();
                 TradingExecutorOpenPendingPositionGrpcResponse {
                     status: error.into(),
+                    reject_reason,
                     position: None,
                 }
             }
@@ -133,12 +189,15 @@ impl TradingExecutorGrpcService for GrpcService {
         let response = match result {
             Ok(position) => TradingExecutorCancelPendingPositionGrpcResponse {
                 status: 0,
+                reject_reason: None,
                 position: Some(position),
             },
             Err(error) => {
+                let reject_reason = Some(error.get_reject_reason().into());
                 let error: TradingExecutorOperationsCodes = error.into();
                 TradingExecutorCancelPendingPositionGrpcResponse {
                     status: error.into(),
+                    reject_reason,
                     position: None,
                 }
             }
@@ -146,33 +205,65 @@ impl TradingExecutorGrpcService for GrpcService {
         Ok(tonic::Response::new(response))
     }
 
+    #[with_telemetry]
+    async fn cancel_all_pending_positions(
+        &self,
+        request: tonic::Request<TradingExecutorCancelAllPendingGrpcRequest>,
+    ) -> Result<tonic::Response<Self::CancelAllPendingPositionsStream>, tonic::Status> {
+        let request = request.into_inner();
+        let result = cancel_all_pending(&self.app, request, my_telemetry).await;
+
+        match result {
+            Ok(receiver) => Ok(tonic::Response::new(Box::pin(
+                tokio_stream::wrappers::ReceiverStream::new(receiver),
+            ))),
+            Err(error) => {
+                let error: TradingExecutorOperationsCodes = error.into();
+                Err(tonic::Status::internal(format!(
+                    "Failed to read pending positions: {:?}",
+                    error
+                )))
+            }
+        }
+    }
+
     #[with_telemetry]
     async fn get_account_pending_positions(
         &self,
         request: tonic::Request<TradingExecutorGetAccountPendingPositionGrpcRequest>,
     ) -> Result<tonic::Response<Self::GetAccountPendingPositionsStream>, tonic::Status> {
         let request = request.into_inner();
+        let query = PositionListQuery::try_from(&request).map_err(into_list_query_status)?;
 
-        let positions = self
-            .app
-            .position_manager_grpc_client
-            .get_account_pending_positions(
-                PositionManagerGetPendingPositionsGrpcRequest {
-                    trader_id: request.trader_id,
-                    account_id: request.account_id,
-                },
-                my_telemetry,
+        let downstream =
+            DownstreamContext::new(&request.trader_id, &request.account_id, "n/a", my_telemetry);
+
+        let positions = downstream
+            .call(
+                DownstreamService::PositionManager,
+                "get_account_pending_positions",
+                self.app
+                    .position_manager_grpc_client
+                    .get_account_pending_positions(
+                        PositionManagerGetPendingPositionsGrpcRequest {
+                            trader_id: request.trader_id.clone(),
+                            account_id: request.account_id.clone(),
+                        },
+                        my_telemetry,
+                    ),
             )
             .await
-            .unwrap();
+            .map_err(into_downstream_status)?;
 
-        let positions = match positions {
-            Some(src) => src,
-            None => vec![],
-        };
+        let positions: Vec<TradingExecutorPendingPositionGrpcModel> = positions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|x| x.into())
+            .collect();
 
-        my_grpc_extensions::grpc_server::send_vec_to_stream(positions.into_iter(), |x| x.into())
-            .await
+        let positions = query.apply(positions).map_err(into_list_query_status)?;
+
+        my_grpc_extensions::grpc_server::send_vec_to_stream(positions.into_iter(), |x| x).await
     }
 
     #[with_telemetry]
@@ -181,26 +272,41 @@ impl TradingExecutorGrpcService for GrpcService {
         request: tonic::Request<TradingExecutorGetActivePositionsGrpcRequest>,
     ) -> Result<tonic::Response<Self::GetAccountActivePositionsStream>, tonic::Status> {
         let request = request.into_inner();
-        let positions = self
-            .app
-            .position_manager_grpc_client
-            .get_account_active_positions(
-                PositionManagerGetActivePositionsGrpcRequest {
-                    trader_id: request.trader_id.clone(),
-                    account_id: request.account_id.clone(),
-                },
-                &my_telemetry,
+        let query: PositionListQuery = (&request).into();
+
+        let downstream = DownstreamContext::new(
+            &request.trader_id,
+            &request.account_id,
+            "n/a",
+            &my_telemetry,
+        );
+
+        let positions = downstream
+            .call(
+                DownstreamService::PositionManager,
+                "get_account_active_positions",
+                self.app
+                    .position_manager_grpc_client
+                    .get_account_active_positions(
+                        PositionManagerGetActivePositionsGrpcRequest {
+                            trader_id: request.trader_id.clone(),
+                            account_id: request.account_id.clone(),
+                        },
+                        &my_telemetry,
+                    ),
             )
             .await
-            .unwrap();
+            .map_err(into_downstream_status)?;
 
-        let positions = match positions {
-            Some(src) => src,
-            None => vec![],
-        };
+        let positions: Vec<TradingExecutorActivePositionGrpcModel> = positions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|x| x.into())
+            .collect();
 
-        my_grpc_extensions::grpc_server::send_vec_to_stream(positions.into_iter(), |x| x.into())
-            .await
+        let positions = query.apply(positions).map_err(into_list_query_status)?;
+
+        my_grpc_extensions::grpc_server::send_vec_to_stream(positions.into_iter(), |x| x).await
     }
 
     #[with_telemetry]
@@ -214,12 +320,15 @@ impl TradingExecutorGrpcService for GrpcService {
         let response: TradingExecutorUpdateSlTpGrpcResponse = match update_sl_tp_result {
             Ok(position) => TradingExecutorUpdateSlTpGrpcResponse {
                 status: 0,
+                reject_reason: None,
                 position: Some(position),
             },
             Err(error) => {
+                let reject_reason = Some(error.get_reject_reason().into());
                 let error: TradingExecutorOperationsCodes = error.into();
                 TradingExecutorUpdateSlTpGrpcResponse {
                     status: error.into(),
+                    reject_reason,
                     position: None,
                 }
             }
@@ -228,7 +337,213 @@ impl TradingExecutorGrpcService for GrpcService {
         Ok(tonic::Response::new(response))
     }
 
+    #[with_telemetry]
+    async fn get_instrument_trading_schedule(
+        &self,
+        request: tonic::Request<TradingExecutorGetInstrumentTradingScheduleGrpcRequest>,
+    ) -> Result<tonic::Response<TradingExecutorInstrumentTradingScheduleGrpcResponse>, tonic::Status>
+    {
+        let request = request.into_inner();
+
+        let Some(instrument) = self
+            .app
+            .trading_instruments_reader
+            .get_entity(
+                TradingInstrumentNoSqlEntity::generate_partition_key(),
+                &request.instrument_id,
+            )
+            .await
+        else {
+            return Ok(tonic::Response::new(
+                TradingExecutorInstrumentTradingScheduleGrpcResponse {
+                    status: TradingExecutorOperationsCodes::InstrumentNotFound as i32,
+                    is_open: false,
+                    next_close_unix_timestamp_milliseconds: None,
+                    next_open_unix_timestamp_milliseconds: None,
+                    weekly_schedule: vec![],
+                    holidays: vec![],
+                    reject_reason: Some(
+                        TradingExecutorError::InstrumentNotFound
+                            .get_reject_reason()
+                            .into(),
+                    ),
+                },
+            ));
+        };
+
+        let schedule =
+            get_instrument_trading_schedule(&self.app, &instrument, chrono::Utc::now()).await;
+
+        let response = TradingExecutorInstrumentTradingScheduleGrpcResponse {
+            status: TradingExecutorOperationsCodes::Ok as i32,
+            reject_reason: None,
+            is_open: schedule.is_open,
+            next_close_unix_timestamp_milliseconds: schedule
+                .next_close
+                .map(|x| x.timestamp_millis() as u64),
+            next_open_unix_timestamp_milliseconds: schedule
+                .next_open
+                .map(|x| x.timestamp_millis() as u64),
+            weekly_schedule: instrument.days_off.iter().map(|x| x.into()).collect(),
+            holidays: schedule
+                .holidays
+                .iter()
+                .map(|(from, to)| TradingExecutorHolidayGrpcModel {
+                    from_unix_timestamp_milliseconds: from.timestamp_millis() as u64,
+                    to_unix_timestamp_milliseconds: to.timestamp_millis() as u64,
+                })
+                .collect(),
+        };
+
+        Ok(tonic::Response::new(response))
+    }
+
+    #[with_telemetry]
+    async fn get_quote(
+        &self,
+        request: tonic::Request<TradingExecutorGetQuoteGrpcRequest>,
+    ) -> Result<tonic::Response<TradingExecutorGetQuoteGrpcResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let result = get_quote(&self.app, request, my_telemetry).await;
+
+        let response = match result {
+            Ok(bid_ask) => TradingExecutorGetQuoteGrpcResponse {
+                status: TradingExecutorOperationsCodes::Ok as i32,
+                reject_reason: None,
+                bid_ask: Some(bid_ask),
+            },
+            Err(error) => {
+                let reject_reason = Some(error.get_reject_reason().into());
+                let error: TradingExecutorOperationsCodes = error.into();
+                TradingExecutorGetQuoteGrpcResponse {
+                    status: error.into(),
+                    reject_reason,
+                    bid_ask: None,
+                }
+            }
+        };
+
+        Ok(tonic::Response::new(response))
+    }
+
+    #[with_telemetry]
+    async fn subscribe_quotes(
+        &self,
+        request: tonic::Request<TradingExecutorSubscribeQuotesGrpcRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeQuotesStream>, tonic::Status> {
+        let request = request.into_inner();
+        let result = subscribe_quotes(&self.app, request, my_telemetry).await;
+
+        match result {
+            Ok(receiver) => Ok(tonic::Response::new(Box::pin(
+                tokio_stream::wrappers::ReceiverStream::new(receiver),
+            ))),
+            Err(error) => {
+                let error: TradingExecutorOperationsCodes = error.into();
+                Err(tonic::Status::failed_precondition(format!(
+                    "Can't subscribe to quotes: {:?}",
+                    error
+                )))
+            }
+        }
+    }
+
+    #[with_telemetry]
+    async fn get_account_summary(
+        &self,
+        request: tonic::Request<TradingExecutorGetAccountSummaryGrpcRequest>,
+    ) -> Result<tonic::Response<TradingExecutorGetAccountSummaryGrpcResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let result = get_account_summary(&self.app, request, my_telemetry).await;
+
+        let response = match result {
+            Ok(summary) => TradingExecutorGetAccountSummaryGrpcResponse {
+                status: TradingExecutorOperationsCodes::Ok as i32,
+                reject_reason: None,
+                summary: Some(summary),
+            },
+            Err(error) => {
+                let reject_reason = Some(error.get_reject_reason().into());
+                let error: TradingExecutorOperationsCodes = error.into();
+                TradingExecutorGetAccountSummaryGrpcResponse {
+                    status: error.into(),
+                    reject_reason,
+                    summary: None,
+                }
+            }
+        };
+
+        Ok(tonic::Response::new(response))
+    }
+
+    #[with_telemetry]
+    async fn subscribe_account_positions(
+        &self,
+        request: tonic::Request<TradingExecutorSubscribeAccountPositionsGrpcRequest>,
+    ) -> Result<tonic::Response<Self::SubscribeAccountPositionsStream>, tonic::Status> {
+        let request = request.into_inner();
+        let result = subscribe_account_positions(&self.app, request, my_telemetry).await;
+
+        match result {
+            Ok(receiver) => Ok(tonic::Response::new(Box::pin(
+                tokio_stream::wrappers::ReceiverStream::new(receiver),
+            ))),
+            Err(error) => {
+                let error: TradingExecutorOperationsCodes = error.into();
+                Err(tonic::Status::failed_precondition(format!(
+                    "Can't subscribe to account positions: {:?}",
+                    error
+                )))
+            }
+        }
+    }
+
+    #[with_telemetry]
+    async fn get_account_closed_positions(
+        &self,
+        request: tonic::Request<TradingExecutorGetAccountClosedPositionsGrpcRequest>,
+    ) -> Result<tonic::Response<TradingExecutorGetAccountClosedPositionsGrpcResponse>, tonic::Status>
+    {
+        let request = request.into_inner();
+        let (positions, summary) = get_account_closed_positions(&self.app, &request).await;
+
+        Ok(tonic::Response::new(
+            TradingExecutorGetAccountClosedPositionsGrpcResponse {
+                status: TradingExecutorOperationsCodes::Ok as i32,
+                positions,
+                summary: Some(summary),
+            },
+        ))
+    }
+
+    #[with_telemetry]
+    async fn check_open_position(
+        &self,
+        request: tonic::Request<TradingExecutorOpenPositionGrpcRequest>,
+    ) -> Result<tonic::Response<TradingExecutorCheckOpenPositionGrpcResponse>, tonic::Status> {
+        let request = request.into_inner();
+        let check = check_open_position(&self.app, &request, my_telemetry).await;
+
+        Ok(tonic::Response::new(check.into()))
+    }
+
     async fn ping(&self, _: tonic::Request<()>) -> Result<tonic::Response<()>, tonic::Status> {
         return Ok(tonic::Response::new(()));
     }
 }
+
+fn into_downstream_status(error: TradingExecutorError) -> tonic::Status {
+    tonic::Status::unavailable(error.get_reject_reason().message)
+}
+
+fn into_list_query_status(error: TradingExecutorError) -> tonic::Status {
+    match error {
+        TradingExecutorError::InvalidCursor | TradingExecutorError::ProfitSortNotSupported => {
+            tonic::Status::invalid_argument(error.get_reject_reason().message)
+        }
+        error => {
+            let error: TradingExecutorOperationsCodes = error.into();
+            tonic::Status::internal(format!("Can't list positions: {:?}", error))
+        }
+    }
+}
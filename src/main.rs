@@ -1,7 +1,9 @@
 mod app;
+mod caches;
 mod flows;
 mod grpc;
 mod models;
+mod nosql;
 mod settings;
 use service_sdk::ServiceInfo;
 pub mod position_manager_grpc {
@@ -18,9 +20,11 @@ pub mod a_book_bridge_grpc {
 }
 
 pub use app::*;
+pub use caches::*;
 pub use flows::*;
 pub use grpc::*;
 pub use models::*;
+pub use nosql::*;
 
 use std::sync::Arc;
 
@@ -32,7 +36,13 @@ async fn main() {
     let settings_reader = Arc::new(settings_reader);
 
     let mut service_context = service_sdk::ServiceContext::new(settings_reader.clone()).await;
-    let app_context = Arc::new(AppContext::new(settings_reader.clone(), &service_context).await);
+    let app_context = match AppContext::new(settings_reader.clone(), &service_context).await {
+        Ok(app_context) => Arc::new(app_context),
+        Err(err) => {
+            eprintln!("Can't start trading executor: {}", err);
+            std::process::exit(1);
+        }
+    };
 
     service_context.configure_grpc_server(|builder| {
         builder.add_grpc_service(TradingExecutorGrpcServiceServer::new(GrpcService::new(
@@ -0,0 +1,67 @@
+#[derive(Debug, Clone, Default)]
+pub struct CommissionSettings {
+    pub percent_of_notional: Option<f64>,
+    pub per_lot: Option<f64>,
+    pub min_fee: Option<f64>,
+    pub currency: Option<String>,
+}
+
+impl CommissionSettings {
+    pub fn is_configured(&self) -> bool {
+        self.percent_of_notional.is_some() || self.per_lot.is_some() || self.min_fee.is_some()
+    }
+
+    /// `notional` has to be in the commission currency. The result is in the same currency.
+    pub fn calculate(&self, notional: f64, lots: f64) -> f64 {
+        let mut fee = 0.0;
+
+        if let Some(percent) = self.percent_of_notional {
+            fee += notional.abs() * percent / 100.0;
+        }
+
+        if let Some(per_lot) = self.per_lot {
+            fee += lots.abs() * per_lot;
+        }
+
+        if let Some(min_fee) = self.min_fee {
+            if fee < min_fee {
+                fee = min_fee;
+            }
+        }
+
+        fee
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CommissionSettings;
+
+    #[test]
+    fn percent_and_per_lot_commission() {
+        let settings = CommissionSettings {
+            percent_of_notional: Some(0.1),
+            per_lot: Some(3.5),
+            min_fee: None,
+            currency: None,
+        };
+
+        let fee = settings.calculate(100_000.0, 2.0);
+
+        assert!((fee - 107.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn min_fee_is_applied() {
+        let settings = CommissionSettings {
+            percent_of_notional: Some(0.1),
+            per_lot: None,
+            min_fee: Some(5.0),
+            currency: None,
+        };
+
+        let fee = settings.calculate(1_000.0, 0.01);
+
+        assert!((fee - 5.0).abs() < 1e-9);
+    }
+}
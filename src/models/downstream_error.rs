@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub enum DownstreamService {
+    PositionManager,
+    AccountsManager,
+    ABookBridge,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownstreamError {
+    pub service: DownstreamService,
+    pub method: &'static str,
+    /// Debug representation of the gRPC client error, including the tonic status.
+    pub status: String,
+    pub elapsed_ms: u64,
+}
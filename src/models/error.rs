@@ -1,11 +1,33 @@
+use crate::{DownstreamError, PriceTimeoutSession};
+
+#[derive(Debug)]
 pub enum TradingExecutorError {
     DayOff,
-    OperationIsTooLow,
-    OperationIsTooHigh,
+    OperationIsTooLow {
+        amount: f64,
+        min_amount: f64,
+    },
+    OperationIsTooHigh {
+        amount: f64,
+        max_amount: f64,
+    },
     MinOperationsByInstrumentViolated,
     MaxOperationsByInstrumentViolated,
     NotEnoughBalance,
+    NotEnoughFreeMargin {
+        free_margin: f64,
+        required: f64,
+    },
+    MarginLevelTooLow {
+        margin_level: f64,
+        min_margin_level: f64,
+    },
     NoLiquidity,
+    PriceIsStale {
+        age_seconds: i64,
+        timeout_seconds: i64,
+        session: PriceTimeoutSession,
+    },
     PositionNotFound,
     TpIsTooClose,
     SlIsTooClose,
@@ -14,11 +36,29 @@ pub enum TradingExecutorError {
     InstrumentIsNotTradable,
     HitMaxAmountOfPendingOrders,
     TechError,
-    MultiplierIsNotFound,
+    MultiplierIsNotFound {
+        leverage: f64,
+        max_leverage: Option<f64>,
+    },
     TradingDisabled,
     MaxPositionsAmount,
     TradingGroupNotFound,
     TradingProfileNotFound,
     TradingProfileInstrumentNotFound,
     ABookReject,
+    MarketClosingSoon {
+        closes_in_seconds: i64,
+        window_seconds: i64,
+    },
+    ProcessIdDuplicate,
+    ToppingUpDisabled,
+    MarginCallSettingsNotFound,
+    Downstream(DownstreamError),
+    InvalidCursor,
+    InvalidSizingValue {
+        value: Option<f64>,
+    },
+    ContractSizeNotConfigured,
+    StopLossRequired,
+    ProfitSortNotSupported,
 }
@@ -0,0 +1,23 @@
+#[derive(Debug, Clone)]
+pub struct ExecutableBidAsk {
+    pub asset_pair: String,
+    pub bid: f64,
+    pub ask: f64,
+    pub date_time_unix_timestamp_milis: u64,
+}
+
+impl ExecutableBidAsk {
+    pub fn get_open_price(&self, is_buy: bool) -> f64 {
+        match is_buy {
+            true => self.ask,
+            false => self.bid,
+        }
+    }
+
+    pub fn get_close_price(&self, is_buy: bool) -> f64 {
+        match is_buy {
+            true => self.bid,
+            false => self.ask,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum leverage for an instrument exposure bracket. A tier without `notional_up_to` covers
+/// everything above the bounded tiers. Without one, exposure above the last bounded tier is
+/// rejected.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct LeverageTier {
+    pub notional_up_to: Option<f64>,
+    pub max_leverage: f64,
+}
+
+/// Returns the maximum leverage for the account exposure in collateral currency. Returns `None`
+/// when no tier covers the exposure, i.e. it is above the last bounded tier and there is no
+/// open-ended tier.
+pub fn get_tier_max_leverage(tiers: &[LeverageTier], exposure: f64) -> Option<f64> {
+    let mut tiers = tiers.to_vec();
+    tiers.sort_by(|a, b| {
+        let a = a.notional_up_to.unwrap_or(f64::INFINITY);
+        let b = b.notional_up_to.unwrap_or(f64::INFINITY);
+        a.total_cmp(&b)
+    });
+
+    tiers
+        .iter()
+        .find(|x| exposure <= x.notional_up_to.unwrap_or(f64::INFINITY))
+        .map(|x| x.max_leverage)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_tier_max_leverage, LeverageTier};
+
+    #[test]
+    fn pick_tier_by_exposure() {
+        let tiers = [
+            LeverageTier {
+                notional_up_to: None,
+                max_leverage: 5.0,
+            },
+            LeverageTier {
+                notional_up_to: Some(100_000.0),
+                max_leverage: 100.0,
+            },
+            LeverageTier {
+                notional_up_to: Some(500_000.0),
+                max_leverage: 20.0,
+            },
+        ];
+
+        assert_eq!(Some(100.0), get_tier_max_leverage(&tiers, 50_000.0));
+        assert_eq!(Some(100.0), get_tier_max_leverage(&tiers, 100_000.0));
+        assert_eq!(Some(20.0), get_tier_max_leverage(&tiers, 100_001.0));
+        assert_eq!(Some(5.0), get_tier_max_leverage(&tiers, 1_000_000.0));
+        assert_eq!(Some(20.0), get_tier_max_leverage(&tiers[1..], 500_000.0));
+        assert_eq!(None, get_tier_max_leverage(&tiers[1..], 1_000_000.0));
+        assert_eq!(None, get_tier_max_leverage(&[], 1_000.0));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::settings::{MarketStateChecksSettings, MarketStatePolicySettings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradingOperationType {
+    Open,
+    Close,
+    PlacePending,
+    CancelPending,
+    ModifySlTp,
+}
+
+impl TradingOperationType {
+    pub fn adds_exposure(&self) -> bool {
+        match self {
+            TradingOperationType::Open | TradingOperationType::PlacePending => true,
+            TradingOperationType::Close
+            | TradingOperationType::CancelPending
+            | TradingOperationType::ModifySlTp => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MarketStateChecks {
+    pub day_off: bool,
+    pub liquidity: bool,
+}
+
+impl MarketStateChecks {
+    pub const ALL: Self = Self {
+        day_off: true,
+        liquidity: true,
+    };
+
+    pub const NONE: Self = Self {
+        day_off: false,
+        liquidity: false,
+    };
+}
+
+impl From<&MarketStateChecksSettings> for MarketStateChecks {
+    fn from(src: &MarketStateChecksSettings) -> Self {
+        Self {
+            day_off: src.validate_day_off,
+            liquidity: src.validate_liquidity,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarketStatePolicy {
+    pub open: MarketStateChecks,
+    pub close: MarketStateChecks,
+    pub place_pending: MarketStateChecks,
+    pub cancel_pending: MarketStateChecks,
+    pub modify_sl_tp: MarketStateChecks,
+}
+
+impl MarketStatePolicy {
+    pub fn get_checks(&self, operation: TradingOperationType) -> MarketStateChecks {
+        match operation {
+            TradingOperationType::Open => self.open,
+            TradingOperationType::Close => self.close,
+            TradingOperationType::PlacePending => self.place_pending,
+            TradingOperationType::CancelPending => self.cancel_pending,
+            TradingOperationType::ModifySlTp => self.modify_sl_tp,
+        }
+    }
+}
+
+impl Default for MarketStatePolicy {
+    fn default() -> Self {
+        Self {
+            open: MarketStateChecks::ALL,
+            close: MarketStateChecks::ALL,
+            place_pending: MarketStateChecks::ALL,
+            cancel_pending: MarketStateChecks::NONE,
+            modify_sl_tp: MarketStateChecks::ALL,
+        }
+    }
+}
+
+impl From<Option<&MarketStatePolicySettings>> for MarketStatePolicy {
+    fn from(src: Option<&MarketStatePolicySettings>) -> Self {
+        let mut result = Self::default();
+
+        let Some(src) = src else {
+            return result;
+        };
+
+        if let Some(checks) = &src.open {
+            result.open = checks.into();
+        }
+
+        if let Some(checks) = &src.close {
+            result.close = checks.into();
+        }
+
+        if let Some(checks) = &src.place_pending {
+            result.place_pending = checks.into();
+        }
+
+        if let Some(checks) = &src.cancel_pending {
+            result.cancel_pending = checks.into();
+        }
+
+        if let Some(checks) = &src.modify_sl_tp {
+            result.modify_sl_tp = checks.into();
+        }
+
+        result
+    }
+}
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupMode {
+    /// Markup is added to the price as is, in quote currency units.
+    PriceOffset,
+    Percent,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MarkupSettings {
+    pub mode: MarkupMode,
+    pub bid: f64,
+    pub ask: f64,
+    pub min_spread: Option<f64>,
+    pub max_spread: Option<f64>,
+}
+
+impl MarkupSettings {
+    /// Executable bid/ask for the raw feed prices. Opens execute at it, closes are settled to it.
+    pub fn apply(&self, raw_bid: f64, raw_ask: f64) -> (f64, f64) {
+        let (bid_markup, ask_markup) = match self.mode {
+            MarkupMode::PriceOffset => (self.bid, self.ask),
+            MarkupMode::Percent => (raw_bid * self.bid / 100.0, raw_ask * self.ask / 100.0),
+        };
+
+        let (bid, ask) = (raw_bid - bid_markup, raw_ask + ask_markup);
+        let spread = ask - bid;
+        let mid = (ask + bid) / 2.0;
+
+        if let Some(min_spread) = self.min_spread {
+            if spread < min_spread {
+                return (mid - min_spread / 2.0, mid + min_spread / 2.0);
+            }
+        }
+
+        // Only the markup is clamped: the quote never gets narrower than the raw feed spread.
+        if let Some(max_spread) = self.max_spread {
+            let markup = bid_markup + ask_markup;
+
+            if spread > max_spread && markup > 0.0 {
+                let raw_spread = raw_ask - raw_bid;
+                let allowed_markup = (max_spread - raw_spread).max(0.0);
+                let ratio = allowed_markup / markup;
+
+                return (raw_bid - bid_markup * ratio, raw_ask + ask_markup * ratio);
+            }
+        }
+
+        (bid, ask)
+    }
+}
+
+/// Amount in quote currency the client pays for the markup on close: the PnL at the raw close
+/// price minus the PnL at the executable one. Negative when the markup improves the price.
+pub fn get_close_markup_cost(
+    is_buy: bool,
+    units: f64,
+    raw_close_price: f64,
+    close_price: f64,
+) -> f64 {
+    match is_buy {
+        true => (raw_close_price - close_price) * units,
+        false => (close_price - raw_close_price) * units,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_close_markup_cost, MarkupMode, MarkupSettings};
+
+    fn assert_price(expected: f64, actual: f64) {
+        assert!(
+            (expected - actual).abs() < 1e-9,
+            "{} != {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn price_offset_markup() {
+        let markup = MarkupSettings {
+            mode: MarkupMode::PriceOffset,
+            bid: 0.0002,
+            ask: 0.0003,
+            min_spread: None,
+            max_spread: None,
+        };
+
+        let (bid, ask) = markup.apply(1.1000, 1.1001);
+
+        assert_price(1.0998, bid);
+        assert_price(1.1004, ask);
+    }
+
+    #[test]
+    fn percent_markup() {
+        let markup = MarkupSettings {
+            mode: MarkupMode::Percent,
+            bid: 1.0,
+            ask: 1.0,
+            min_spread: None,
+            max_spread: None,
+        };
+
+        let (bid, ask) = markup.apply(100.0, 100.0);
+
+        assert_price(99.0, bid);
+        assert_price(101.0, ask);
+    }
+
+    #[test]
+    fn min_spread_widens_around_mid() {
+        let markup = MarkupSettings {
+            mode: MarkupMode::PriceOffset,
+            bid: 0.0,
+            ask: 0.0,
+            min_spread: Some(0.0010),
+            max_spread: None,
+        };
+
+        let (bid, ask) = markup.apply(1.1000, 1.1002);
+
+        assert_price(1.0996, bid);
+        assert_price(1.1006, ask);
+    }
+
+    #[test]
+    fn max_spread_narrows_around_mid() {
+        let markup = MarkupSettings {
+            mode: MarkupMode::Percent,
+            bid: 1.0,
+            ask: 1.0,
+            min_spread: None,
+            max_spread: Some(1.0),
+        };
+
+        let (bid, ask) = markup.apply(100.0, 100.0);
+
+        assert_price(99.5, bid);
+        assert_price(100.5, ask);
+    }
+
+    #[test]
+    fn max_spread_never_narrows_raw_spread() {
+        let markup = MarkupSettings {
+            mode: MarkupMode::PriceOffset,
+            bid: 0.5,
+            ask: 0.5,
+            min_spread: None,
+            max_spread: Some(1.0),
+        };
+
+        let (bid, ask) = markup.apply(100.0, 102.0);
+
+        assert_price(100.0, bid);
+        assert_price(102.0, ask);
+    }
+
+    #[test]
+    fn close_markup_cost_for_buy_and_sell() {
+        let markup = MarkupSettings {
+            mode: MarkupMode::PriceOffset,
+            bid: 0.0002,
+            ask: 0.0003,
+            min_spread: None,
+            max_spread: None,
+        };
+
+        let (raw_bid, raw_ask) = (1.1000, 1.1001);
+        let (bid, ask) = markup.apply(raw_bid, raw_ask);
+
+        // Buy closes at the bid, sell at the ask. Both lose the markup on 10 000 units.
+        assert_price(2.0, get_close_markup_cost(true, 10_000.0, raw_bid, bid));
+        assert_price(3.0, get_close_markup_cost(false, 10_000.0, raw_ask, ask));
+    }
+
+    #[test]
+    fn close_markup_cost_is_zero_without_markup() {
+        let markup = MarkupSettings {
+            mode: MarkupMode::Percent,
+            bid: 0.0,
+            ask: 0.0,
+            min_spread: None,
+            max_spread: None,
+        };
+
+        let (bid, ask) = markup.apply(100.0, 100.5);
+
+        assert_price(0.0, get_close_markup_cost(true, 10.0, 100.0, bid));
+        assert_price(0.0, get_close_markup_cost(false, 10.0, 100.5, ask));
+    }
+}
@@ -1,3 +1,21 @@
+mod commission_settings;
+mod downstream_error;
 mod error;
+mod executable_bid_ask;
+mod leverage_tier;
+mod markup_settings;
+mod market_state_policy;
+mod position_list_query;
+mod price_timeout_session;
+mod reject_reason;
 
+pub use commission_settings::*;
+pub use downstream_error::*;
 pub use error::*;
+pub use executable_bid_ask::*;
+pub use leverage_tier::*;
+pub use markup_settings::*;
+pub use market_state_policy::*;
+pub use position_list_query::*;
+pub use price_timeout_session::*;
+pub use reject_reason::*;
@@ -0,0 +1,343 @@
+use std::cmp::Ordering;
+
+use crate::{
+    trading_executor_grpc::{
+        TradingExecutorActivePositionGrpcModel,
+        TradingExecutorGetAccountPendingPositionGrpcRequest,
+        TradingExecutorGetActivePositionsGrpcRequest, TradingExecutorPendingPositionGrpcModel,
+        TradingExecutorPositionSortField,
+    },
+    TradingExecutorError,
+};
+
+pub trait ListedPosition {
+    fn get_id(&self) -> &str;
+    fn get_asset_pair(&self) -> &str;
+    fn get_side(&self) -> i32;
+    fn get_create_date(&self) -> u64;
+    /// Pending positions have no profit yet.
+    fn get_profit(&self) -> Option<f64>;
+    fn get_invest_amount(&self) -> f64;
+}
+
+impl ListedPosition for TradingExecutorActivePositionGrpcModel {
+    fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    fn get_asset_pair(&self) -> &str {
+        &self.asset_pair
+    }
+
+    fn get_side(&self) -> i32 {
+        self.side
+    }
+
+    fn get_create_date(&self) -> u64 {
+        self.create_date_unix_timestamp_milliseconds
+    }
+
+    fn get_profit(&self) -> Option<f64> {
+        Some(self.profit)
+    }
+
+    fn get_invest_amount(&self) -> f64 {
+        self.invest_amount
+    }
+}
+
+impl ListedPosition for TradingExecutorPendingPositionGrpcModel {
+    fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    fn get_asset_pair(&self) -> &str {
+        &self.asset_pair
+    }
+
+    fn get_side(&self) -> i32 {
+        self.side
+    }
+
+    fn get_create_date(&self) -> u64 {
+        self.create_date_unix_timestamp_milliseconds
+    }
+
+    fn get_profit(&self) -> Option<f64> {
+        None
+    }
+
+    fn get_invest_amount(&self) -> f64 {
+        self.invest_amount
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PositionListQuery {
+    pub asset_pair: Option<String>,
+    pub side: Option<i32>,
+    pub opened_after: Option<u64>,
+    pub min_profit: Option<f64>,
+    pub sort_by: TradingExecutorPositionSortField,
+    pub sort_descending: bool,
+    /// Id of the last position of the previous page.
+    pub cursor: Option<String>,
+    /// Sort value of the last position of the previous page, as the client received it.
+    pub cursor_sort_value: Option<f64>,
+    pub limit: Option<usize>,
+}
+
+impl PositionListQuery {
+    /// Keyset pagination: the page starts right after the (sort value, id) of the cursor, so
+    /// it works when the cursor position is closed or its profit has moved since. Equal sort
+    /// values are ordered by position id.
+    pub fn apply<T: ListedPosition>(
+        &self,
+        positions: Vec<T>,
+    ) -> Result<Vec<T>, TradingExecutorError> {
+        let cursor = match (&self.cursor, self.cursor_sort_value) {
+            (Some(id), Some(sort_value)) => Some((sort_value, id.as_str())),
+            (None, None) => None,
+            _ => return Err(TradingExecutorError::InvalidCursor),
+        };
+
+        let mut positions: Vec<T> = positions
+            .into_iter()
+            .filter(|position| self.is_matched(position))
+            .filter(|position| match cursor {
+                Some(cursor) => self.is_after_cursor(position, cursor),
+                None => true,
+            })
+            .collect();
+
+        positions.sort_by(|a, b| {
+            let ordering = compare_keys(
+                (self.get_sort_value(a), a.get_id()),
+                (self.get_sort_value(b), b.get_id()),
+            );
+            match self.sort_descending {
+                true => ordering.reverse(),
+                false => ordering,
+            }
+        });
+
+        let limit = self.limit.unwrap_or(usize::MAX);
+
+        Ok(positions.into_iter().take(limit).collect())
+    }
+
+    fn is_after_cursor(&self, position: &impl ListedPosition, cursor: (f64, &str)) -> bool {
+        let ordering = compare_keys((self.get_sort_value(position), position.get_id()), cursor);
+
+        match self.sort_descending {
+            true => ordering == Ordering::Less,
+            false => ordering == Ordering::Greater,
+        }
+    }
+
+    fn is_matched(&self, position: &impl ListedPosition) -> bool {
+        if let Some(asset_pair) = &self.asset_pair {
+            if position.get_asset_pair() != asset_pair {
+                return false;
+            }
+        }
+
+        if let Some(side) = self.side {
+            if position.get_side() != side {
+                return false;
+            }
+        }
+
+        if let Some(opened_after) = self.opened_after {
+            if position.get_create_date() <= opened_after {
+                return false;
+            }
+        }
+
+        if let (Some(min_profit), Some(profit)) = (self.min_profit, position.get_profit()) {
+            if profit < min_profit {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn get_sort_value(&self, position: &impl ListedPosition) -> f64 {
+        match self.sort_by {
+            TradingExecutorPositionSortField::CreateDate => position.get_create_date() as f64,
+            TradingExecutorPositionSortField::Profit => position.get_profit().unwrap_or_default(),
+            TradingExecutorPositionSortField::InvestAmount => position.get_invest_amount(),
+        }
+    }
+}
+
+fn compare_keys(a: (f64, &str), b: (f64, &str)) -> Ordering {
+    a.0.total_cmp(&b.0).then_with(|| a.1.cmp(b.1))
+}
+
+impl From<&TradingExecutorGetActivePositionsGrpcRequest> for PositionListQuery {
+    fn from(src: &TradingExecutorGetActivePositionsGrpcRequest) -> Self {
+        Self {
+            asset_pair: src.asset_pair.clone(),
+            side: src.side,
+            opened_after: src.opened_after_unix_timestamp_milliseconds,
+            min_profit: src.min_profit,
+            sort_by: src.sort_by(),
+            sort_descending: src.sort_descending,
+            cursor: src.cursor.clone(),
+            cursor_sort_value: src.cursor_sort_value,
+            limit: src.limit.map(|x| x as usize),
+        }
+    }
+}
+
+/// Pending positions have no profit, so the profit sort is rejected instead of ordering them
+/// by zero.
+impl TryFrom<&TradingExecutorGetAccountPendingPositionGrpcRequest> for PositionListQuery {
+    type Error = TradingExecutorError;
+
+    fn try_from(
+        src: &TradingExecutorGetAccountPendingPositionGrpcRequest,
+    ) -> Result<Self, Self::Error> {
+        if src.sort_by() == TradingExecutorPositionSortField::Profit {
+            return Err(TradingExecutorError::ProfitSortNotSupported);
+        }
+
+        Ok(Self {
+            asset_pair: src.asset_pair.clone(),
+            side: src.side,
+            opened_after: src.opened_after_unix_timestamp_milliseconds,
+            min_profit: None,
+            sort_by: src.sort_by(),
+            sort_descending: src.sort_descending,
+            cursor: src.cursor.clone(),
+            cursor_sort_value: src.cursor_sort_value,
+            limit: src.limit.map(|x| x as usize),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        trading_executor_grpc::{
+            TradingExecutorActivePositionGrpcModel,
+            TradingExecutorGetAccountPendingPositionGrpcRequest, TradingExecutorPositionSide,
+            TradingExecutorPositionSortField,
+        },
+        TradingExecutorError,
+    };
+
+    use super::PositionListQuery;
+
+    fn position(
+        id: &str,
+        asset_pair: &str,
+        create_date: u64,
+        profit: f64,
+    ) -> TradingExecutorActivePositionGrpcModel {
+        TradingExecutorActivePositionGrpcModel {
+            id: id.to_string(),
+            asset_pair: asset_pair.to_string(),
+            side: TradingExecutorPositionSide::Buy as i32,
+            create_date_unix_timestamp_milliseconds: create_date,
+            profit,
+            ..Default::default()
+        }
+    }
+
+    fn query() -> PositionListQuery {
+        PositionListQuery {
+            asset_pair: None,
+            side: None,
+            opened_after: None,
+            min_profit: None,
+            sort_by: TradingExecutorPositionSortField::CreateDate,
+            sort_descending: false,
+            cursor: None,
+            cursor_sort_value: None,
+            limit: None,
+        }
+    }
+
+    fn positions() -> Vec<TradingExecutorActivePositionGrpcModel> {
+        vec![
+            position("1", "EURUSD", 300, 10.0),
+            position("2", "XAUUSD", 100, -5.0),
+            position("3", "EURUSD", 200, 3.0),
+            position("4", "EURUSD", 400, -1.0),
+        ]
+    }
+
+    fn ids(positions: Vec<TradingExecutorActivePositionGrpcModel>) -> Vec<String> {
+        positions.into_iter().map(|x| x.id).collect()
+    }
+
+    #[test]
+    fn filter_by_instrument_and_profit() {
+        let mut query = query();
+        query.asset_pair = Some("EURUSD".to_string());
+        query.min_profit = Some(0.0);
+
+        let result = query.apply(positions()).unwrap();
+
+        assert_eq!(vec!["3", "1"], ids(result));
+    }
+
+    #[test]
+    fn paginate_by_profit_descending() {
+        let mut query = query();
+        query.sort_by = TradingExecutorPositionSortField::Profit;
+        query.sort_descending = true;
+        query.limit = Some(2);
+
+        let first_page = query.apply(positions()).unwrap();
+        assert_eq!(vec!["1", "3"], ids(first_page));
+
+        query.cursor = Some("3".to_string());
+        query.cursor_sort_value = Some(3.0);
+        let second_page = query.apply(positions()).unwrap();
+        assert_eq!(vec!["4", "2"], ids(second_page));
+    }
+
+    #[test]
+    fn paginate_when_cursor_position_is_gone() {
+        let mut query = query();
+        query.sort_by = TradingExecutorPositionSortField::Profit;
+        query.sort_descending = true;
+        query.cursor = Some("3".to_string());
+        query.cursor_sort_value = Some(3.0);
+
+        let mut positions = positions();
+        positions.retain(|x| x.id != "3");
+        positions[0].profit = 5.0;
+
+        let second_page = query.apply(positions).unwrap();
+        assert_eq!(vec!["4", "2"], ids(second_page));
+    }
+
+    #[test]
+    fn reject_cursor_without_sort_value() {
+        let mut query = query();
+        query.cursor = Some("2".to_string());
+
+        match query.apply(positions()) {
+            Err(TradingExecutorError::InvalidCursor) => {}
+            _ => panic!("Cursor must be rejected"),
+        }
+    }
+
+    #[test]
+    fn reject_profit_sort_of_pending_positions() {
+        let request = TradingExecutorGetAccountPendingPositionGrpcRequest {
+            sort_by: TradingExecutorPositionSortField::Profit as i32,
+            ..Default::default()
+        };
+
+        match PositionListQuery::try_from(&request) {
+            Err(TradingExecutorError::ProfitSortNotSupported) => {}
+            _ => panic!("Profit sort must be rejected"),
+        }
+    }
+}
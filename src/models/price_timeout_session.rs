@@ -0,0 +1,17 @@
+/// Trading session used to pick the price timeout. Holiday dates use the night timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceTimeoutSession {
+    Day,
+    Night,
+    Holiday,
+}
+
+impl PriceTimeoutSession {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceTimeoutSession::Day => "day",
+            PriceTimeoutSession::Night => "night",
+            PriceTimeoutSession::Holiday => "holiday",
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{PriceTimeoutSession, TradingExecutorError};
+
+/// Structured explanation of a rejected operation. `rule_id` is stable and is also used to
+/// build the localisation key, so it must not be renamed once clients rely on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectReason {
+    pub rule_id: &'static str,
+    pub value: Option<f64>,
+    pub limit: Option<f64>,
+    pub message: String,
+}
+
+impl RejectReason {
+    fn new(rule_id: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            rule_id,
+            value: None,
+            limit: None,
+            message: message.into(),
+        }
+    }
+
+    fn with_values(
+        rule_id: &'static str,
+        value: f64,
+        limit: Option<f64>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            rule_id,
+            value: Some(value),
+            limit,
+            message: message.into(),
+        }
+    }
+
+    pub fn get_localization_key(&self) -> String {
+        format!("trading_executor.reject.{}", self.rule_id)
+    }
+}
+
+impl TradingExecutorError {
+    pub fn get_reject_reason(&self) -> RejectReason {
+        match self {
+            TradingExecutorError::DayOff => {
+                RejectReason::new("day_off", "Instrument is in a day off")
+            }
+            TradingExecutorError::OperationIsTooLow { amount, min_amount } => {
+                RejectReason::with_values(
+                    "operation_too_low",
+                    *amount,
+                    Some(*min_amount),
+                    format!(
+                        "Operation amount {} is below the minimum {}",
+                        amount, min_amount
+                    ),
+                )
+            }
+            TradingExecutorError::OperationIsTooHigh { amount, max_amount } => {
+                RejectReason::with_values(
+                    "operation_too_high",
+                    *amount,
+                    Some(*max_amount),
+                    format!(
+                        "Operation amount {} is above the maximum {}",
+                        amount, max_amount
+                    ),
+                )
+            }
+            TradingExecutorError::MinOperationsByInstrumentViolated => RejectReason::new(
+                "min_operations_by_instrument",
+                "Minimum operations by instrument is violated",
+            ),
+            TradingExecutorError::MaxOperationsByInstrumentViolated => RejectReason::new(
+                "max_operations_by_instrument",
+                "Maximum operations by instrument is violated",
+            ),
+            TradingExecutorError::NotEnoughBalance => {
+                RejectReason::new("not_enough_balance", "Not enough balance")
+            }
+            TradingExecutorError::NotEnoughFreeMargin {
+                free_margin,
+                required,
+            } => RejectReason::with_values(
+                "not_enough_free_margin",
+                *free_margin,
+                Some(*required),
+                format!(
+                    "Free margin {} is less than required {}",
+                    free_margin, required
+                ),
+            ),
+            TradingExecutorError::MarginLevelTooLow {
+                margin_level,
+                min_margin_level,
+            } => RejectReason::with_values(
+                "margin_level_too_low",
+                *margin_level,
+                Some(*min_margin_level),
+                format!(
+                    "Margin level after open {:.2}% is below {:.2}%",
+                    margin_level, min_margin_level
+                ),
+            ),
+            TradingExecutorError::NoLiquidity => {
+                RejectReason::new("no_liquidity", "No liquidity for instrument")
+            }
+            TradingExecutorError::PriceIsStale {
+                age_seconds,
+                timeout_seconds,
+                session,
+            } => RejectReason::with_values(
+                match session {
+                    PriceTimeoutSession::Day => "price_is_stale_day",
+                    PriceTimeoutSession::Night => "price_is_stale_night",
+                    PriceTimeoutSession::Holiday => "price_is_stale_holiday",
+                },
+                *age_seconds as f64,
+                Some(*timeout_seconds as f64),
+                format!(
+                    "Last price is {} sec old, {} session timeout is {} sec",
+                    age_seconds,
+                    session.as_str(),
+                    timeout_seconds
+                ),
+            ),
+            TradingExecutorError::PositionNotFound => {
+                RejectReason::new("position_not_found", "Position not found")
+            }
+            TradingExecutorError::TpIsTooClose => {
+                RejectReason::new("tp_too_close", "Take profit is too close to the price")
+            }
+            TradingExecutorError::SlIsTooClose => {
+                RejectReason::new("sl_too_close", "Stop loss is too close to the price")
+            }
+            TradingExecutorError::AccountNotFound => {
+                RejectReason::new("account_not_found", "Account not found")
+            }
+            TradingExecutorError::InstrumentNotFound => {
+                RejectReason::new("instrument_not_found", "Instrument not found")
+            }
+            TradingExecutorError::InstrumentIsNotTradable => {
+                RejectReason::new("instrument_not_tradable", "Instrument is not tradable")
+            }
+            TradingExecutorError::HitMaxAmountOfPendingOrders => RejectReason::new(
+                "max_pending_orders",
+                "Maximum amount of pending orders is reached",
+            ),
+            TradingExecutorError::TechError => RejectReason::new("tech_error", "Technical error"),
+            TradingExecutorError::MultiplierIsNotFound {
+                leverage,
+                max_leverage,
+            } => RejectReason::with_values(
+                "leverage_not_allowed",
+                *leverage,
+                *max_leverage,
+                match max_leverage {
+                    Some(max_leverage) => format!(
+                        "Leverage {} is not allowed, maximum is {}",
+                        leverage, max_leverage
+                    ),
+                    None => format!("Leverage {} is not allowed", leverage),
+                },
+            ),
+            TradingExecutorError::TradingDisabled => {
+                RejectReason::new("trading_disabled", "Trading is disabled")
+            }
+            TradingExecutorError::MaxPositionsAmount => {
+                RejectReason::new("max_positions", "Maximum amount of positions is reached")
+            }
+            TradingExecutorError::TradingGroupNotFound => {
+                RejectReason::new("trading_group_not_found", "Trading group not found")
+            }
+            TradingExecutorError::TradingProfileNotFound => {
+                RejectReason::new("trading_profile_not_found", "Trading profile not found")
+            }
+            TradingExecutorError::TradingProfileInstrumentNotFound => RejectReason::new(
+                "trading_profile_instrument_not_found",
+                "Instrument is not available in trading profile",
+            ),
+            TradingExecutorError::ABookReject => {
+                RejectReason::new("a_book_reject", "Rejected by liquidity provider")
+            }
+            TradingExecutorError::MarketClosingSoon {
+                closes_in_seconds,
+                window_seconds,
+            } => RejectReason::with_values(
+                "market_closing_soon",
+                *closes_in_seconds as f64,
+                Some(*window_seconds as f64),
+                format!("Market closes in {} sec", closes_in_seconds),
+            ),
+            TradingExecutorError::ProcessIdDuplicate => {
+                RejectReason::new("process_id_duplicate", "Process id is already used")
+            }
+            TradingExecutorError::ToppingUpDisabled => {
+                RejectReason::new("topping_up_disabled", "Topping up is disabled for position")
+            }
+            TradingExecutorError::MarginCallSettingsNotFound => RejectReason::new(
+                "margin_call_settings_not_found",
+                "Margin call settings not found",
+            ),
+            TradingExecutorError::Downstream(error) => RejectReason::new(
+                "downstream_unavailable",
+                format!("{:?}.{} is unavailable", error.service, error.method),
+            ),
+            TradingExecutorError::InvalidCursor => RejectReason::new(
+                "invalid_cursor",
+                "Cursor must have the position id and the sort value",
+            ),
+            TradingExecutorError::InvalidSizingValue { value } => match value {
+                Some(value) => RejectReason::with_values(
+                    "invalid_sizing_value",
+                    *value,
+                    None,
+                    format!("Sizing value {} must be a positive number", value),
+                ),
+                None => RejectReason::new(
+                    "invalid_sizing_value",
+                    "Sizing value is required for the sizing mode",
+                ),
+            },
+            TradingExecutorError::ContractSizeNotConfigured => RejectReason::new(
+                "contract_size_not_configured",
+                "Contract size is not configured for instrument",
+            ),
+            TradingExecutorError::StopLossRequired => RejectReason::new(
+                "stop_loss_required",
+                "Stop loss is required for risk percent sizing",
+            ),
+            TradingExecutorError::ProfitSortNotSupported => RejectReason::new(
+                "profit_sort_not_supported",
+                "Pending positions have no profit to sort by",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{DownstreamError, DownstreamService, PriceTimeoutSession, TradingExecutorError};
+
+    #[test]
+    fn reject_reason_carries_value_and_limit() {
+        let reason = TradingExecutorError::MultiplierIsNotFound {
+            leverage: 500.0,
+            max_leverage: Some(100.0),
+        }
+        .get_reject_reason();
+
+        assert_eq!("leverage_not_allowed", reason.rule_id);
+        assert_eq!(Some(500.0), reason.value);
+        assert_eq!(Some(100.0), reason.limit);
+        assert_eq!(
+            "trading_executor.reject.leverage_not_allowed",
+            reason.get_localization_key()
+        );
+    }
+
+    #[test]
+    fn reject_reason_names_downstream_call() {
+        let reason = TradingExecutorError::Downstream(DownstreamError {
+            service: DownstreamService::PositionManager,
+            method: "open_position",
+            status: "Unavailable".to_string(),
+            elapsed_ms: 10,
+        })
+        .get_reject_reason();
+
+        assert_eq!(None, reason.value);
+        assert_eq!(
+            "PositionManager.open_position is unavailable",
+            reason.message
+        );
+    }
+
+    #[test]
+    fn stale_price_reject_reason_names_session() {
+        let reason = TradingExecutorError::PriceIsStale {
+            age_seconds: 40,
+            timeout_seconds: 30,
+            session: PriceTimeoutSession::Holiday,
+        }
+        .get_reject_reason();
+
+        assert_eq!("price_is_stale_holiday", reason.rule_id);
+        assert_eq!(
+            "Last price is 40 sec old, holiday session timeout is 30 sec",
+            reason.message
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+service_sdk::macros::use_my_no_sql_entity!();
+
+/// Leverages allowed for an account, overriding the trading profile list. Partition key is the
+/// account id, row key is the instrument id or `*` for every instrument of the account.
+#[my_no_sql_entity("account-leverages")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct AccountLeverageNoSqlEntity {
+    pub leverages: Vec<f64>,
+}
+
+impl AccountLeverageNoSqlEntity {
+    pub const ALL_INSTRUMENTS_ROW_KEY: &'static str = "*";
+
+    pub fn generate_partition_key(account_id: &str) -> &str {
+        account_id
+    }
+}
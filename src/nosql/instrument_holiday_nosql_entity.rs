@@ -0,0 +1,32 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+service_sdk::macros::use_my_no_sql_entity!();
+
+#[my_no_sql_entity("instrument-holidays")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct InstrumentHolidayNoSqlEntity {
+    pub date_from: String,
+    pub date_to: String,
+    pub description: Option<String>,
+}
+
+impl InstrumentHolidayNoSqlEntity {
+    pub const ALL_INSTRUMENTS_PARTITION_KEY: &'static str = "*";
+
+    pub fn get_date_from(&self) -> Option<NaiveDateTime> {
+        self.date_from.parse().ok()
+    }
+
+    pub fn get_date_to(&self) -> Option<NaiveDateTime> {
+        self.date_to.parse().ok()
+    }
+
+    pub fn get_interval(&self, timezone: &Tz) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let from = timezone.from_local_datetime(&self.get_date_from()?).earliest()?;
+        let to = timezone.from_local_datetime(&self.get_date_to()?).latest()?;
+
+        Some((from.with_timezone(&Utc), to.with_timezone(&Utc)))
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveTime;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+service_sdk::macros::use_my_no_sql_entity!();
+
+#[my_no_sql_entity("instrument-trading-settings")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct InstrumentTradingSettingsNoSqlEntity {
+    pub pre_close_minutes: Option<i32>,
+    pub timezone: Option<String>,
+    pub day_session_from: Option<String>,
+    pub day_session_to: Option<String>,
+    pub contract_size: Option<f64>,
+}
+
+impl InstrumentTradingSettingsNoSqlEntity {
+    pub fn generate_partition_key() -> &'static str {
+        "i"
+    }
+
+    /// UTC when no timezone is set. An unparsable timezone is returned as an error.
+    pub fn get_timezone(&self) -> Result<Tz, String> {
+        let Some(timezone) = &self.timezone else {
+            return Ok(Tz::UTC);
+        };
+
+        timezone.parse().map_err(|err| format!("{}", err))
+    }
+
+    pub fn get_day_session(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let from = self.day_session_from.as_ref()?.parse().ok()?;
+        let to = self.day_session_to.as_ref()?.parse().ok()?;
+
+        Some((from, to))
+    }
+
+    pub fn get_contract_size(&self) -> Option<f64> {
+        self.contract_size.filter(|x| *x > 0.0)
+    }
+}
@@ -0,0 +1,9 @@
+mod account_leverage_nosql_entity;
+mod instrument_holiday_nosql_entity;
+mod instrument_trading_settings_nosql_entity;
+mod trading_profile_instrument_settings_nosql_entity;
+
+pub use account_leverage_nosql_entity::*;
+pub use instrument_holiday_nosql_entity::*;
+pub use instrument_trading_settings_nosql_entity::*;
+pub use trading_profile_instrument_settings_nosql_entity::*;
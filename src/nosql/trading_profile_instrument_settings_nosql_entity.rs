@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{CommissionSettings, LeverageTier, MarkupMode, MarkupSettings};
+service_sdk::macros::use_my_no_sql_entity!();
+
+#[my_no_sql_entity("trading-profile-instrument-settings")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct TradingProfileInstrumentSettingsNoSqlEntity {
+    pub markup_mode: Option<MarkupMode>,
+    pub markup_bid: Option<f64>,
+    pub markup_ask: Option<f64>,
+    pub min_spread: Option<f64>,
+    pub max_spread: Option<f64>,
+    pub commission_percent: Option<f64>,
+    pub commission_per_lot: Option<f64>,
+    pub commission_min: Option<f64>,
+    pub commission_currency: Option<String>,
+    pub min_invest_amount: Option<f64>,
+    pub max_invest_amount: Option<f64>,
+    pub leverage_tiers: Option<Vec<LeverageTier>>,
+    /// Replaces the integer leverage list of the trading profile instrument, so fractional
+    /// leverages can be offered.
+    pub leverages: Option<Vec<f64>>,
+    /// Lowest margin level in percent an open may leave the account at. Falls back to the
+    /// trading profile margin call percent.
+    pub open_min_margin_level: Option<f64>,
+}
+
+impl TradingProfileInstrumentSettingsNoSqlEntity {
+    pub fn get_trading_profile_id(&self) -> &str {
+        &self.partition_key
+    }
+
+    pub fn get_instrument_id(&self) -> &str {
+        &self.row_key
+    }
+
+    pub fn get_markup_settings(&self) -> MarkupSettings {
+        MarkupSettings {
+            mode: self.markup_mode.unwrap_or(MarkupMode::PriceOffset),
+            bid: self.markup_bid.unwrap_or(0.0),
+            ask: self.markup_ask.unwrap_or(0.0),
+            min_spread: self.min_spread,
+            max_spread: self.max_spread,
+        }
+    }
+
+    pub fn get_commission_settings(&self) -> CommissionSettings {
+        CommissionSettings {
+            percent_of_notional: self.commission_percent,
+            per_lot: self.commission_per_lot,
+            min_fee: self.commission_min,
+            currency: self.commission_currency.clone(),
+        }
+    }
+}
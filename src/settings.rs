@@ -19,4 +19,22 @@ pub struct SettingsModel {
     pub my_no_sql_tcp_reader: String,
     pub seq_conn_string: String,
     pub my_telemetry: String,
+    pub market_state_policy: Option<MarketStatePolicySettings>,
+    pub closed_positions_history_path: Option<String>,
+    pub closed_positions_history_retention_days: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MarketStatePolicySettings {
+    pub open: Option<MarketStateChecksSettings>,
+    pub close: Option<MarketStateChecksSettings>,
+    pub place_pending: Option<MarketStateChecksSettings>,
+    pub cancel_pending: Option<MarketStateChecksSettings>,
+    pub modify_sl_tp: Option<MarketStateChecksSettings>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MarketStateChecksSettings {
+    pub validate_day_off: bool,
+    pub validate_liquidity: bool,
 }